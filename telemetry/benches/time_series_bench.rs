@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use avila_telemetry::{TimeSeries, AnomalyDetector, Forecaster, ExponentialSmoothing};
+use avila_telemetry::{TimeSeries, AnomalyDetector, FeatureExtractor, Forecaster, ExponentialSmoothing};
 
 fn bench_moving_average(c: &mut Criterion) {
     let data: Vec<f64> = (0..1000).map(|x| x as f64).collect();
@@ -10,6 +10,19 @@ fn bench_moving_average(c: &mut Criterion) {
     });
 }
 
+fn bench_rolling_windows(c: &mut Criterion) {
+    let data: Vec<f64> = (0..100_000).map(|x| (x as f64 / 50.0).sin() * 10.0 + (x % 7) as f64).collect();
+    let ts = TimeSeries::new(data);
+
+    c.bench_function("rolling_statistics_100k_w1000", |b| {
+        b.iter(|| FeatureExtractor::rolling_statistics(&ts, black_box(1000)))
+    });
+
+    c.bench_function("rolling_median_100k_w1000", |b| {
+        b.iter(|| ts.rolling_median(black_box(1000)))
+    });
+}
+
 fn bench_exponential_moving_average(c: &mut Criterion) {
     let data: Vec<f64> = (0..1000).map(|x| x as f64).collect();
     let ts = TimeSeries::new(data);
@@ -49,6 +62,7 @@ fn bench_forecasting(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_moving_average,
+    bench_rolling_windows,
     bench_exponential_moving_average,
     bench_anomaly_detection,
     bench_forecasting
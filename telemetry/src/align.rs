@@ -0,0 +1,180 @@
+//! Joining and aligning series on their timestamps
+//!
+//! All inputs must carry strictly increasing timestamps. The result is a
+//! [`MultiTimeSeries`] with one column per input (named after the series,
+//! or `col{i}`); unmatched cells are missing (`NaN`) unless a
+//! [`FillMethod`] is given.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{Result, TelemetryError, TimeSeries};
+use crate::missing::FillMethod;
+use crate::multi_series::MultiTimeSeries;
+
+/// Which timestamps appear in a join result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    /// Timestamps present in every series
+    Inner,
+    /// Timestamps present in any series
+    Outer,
+    /// Timestamps of the first series
+    Left,
+}
+
+/// Timestamps of a series, checked to be present and strictly increasing
+fn sorted_timestamps(ts: &TimeSeries) -> Result<&[DateTime<Utc>]> {
+    let timestamps = ts.timestamps.as_deref().ok_or_else(|| {
+        TelemetryError::InvalidData("Joining requires timestamps".to_string())
+    })?;
+    if timestamps.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(TelemetryError::InvalidData(
+            "Timestamps must be strictly increasing".to_string(),
+        ));
+    }
+    Ok(timestamps)
+}
+
+fn column_name(ts: &TimeSeries, i: usize) -> String {
+    ts.name.clone().unwrap_or_else(|| format!("col{}", i))
+}
+
+fn build(index: Vec<DateTime<Utc>>, columns: Vec<(String, Vec<f64>)>, fill: Option<FillMethod>) -> Result<MultiTimeSeries> {
+    let mut result = MultiTimeSeries::new().with_timestamps(index)?;
+    for (name, values) in columns {
+        result.add_column(name, values)?;
+    }
+
+    match fill {
+        Some(method) => result.map_columns(|ts| Ok(ts.fill_missing(method, None)?.values)),
+        None => Ok(result),
+    }
+}
+
+/// Join series on exact timestamp matches
+pub fn join(series: &[TimeSeries], how: JoinType, fill: Option<FillMethod>) -> Result<MultiTimeSeries> {
+    if series.is_empty() {
+        return Err(TelemetryError::InsufficientData(
+            "Need at least one series to join".to_string(),
+        ));
+    }
+    let all: Vec<&[DateTime<Utc>]> = series.iter().map(sorted_timestamps).collect::<Result<_>>()?;
+
+    let index: Vec<DateTime<Utc>> = match how {
+        JoinType::Left => all[0].to_vec(),
+        JoinType::Inner => all[0]
+            .iter()
+            .filter(|t| all[1..].iter().all(|other| other.binary_search(t).is_ok()))
+            .copied()
+            .collect(),
+        JoinType::Outer => {
+            let mut union: Vec<DateTime<Utc>> = all.iter().flat_map(|ts| ts.iter().copied()).collect();
+            union.sort();
+            union.dedup();
+            union
+        }
+    };
+
+    let columns = series
+        .iter()
+        .zip(&all)
+        .enumerate()
+        .map(|(i, (ts, timestamps))| {
+            let values = index
+                .iter()
+                .map(|t| timestamps.binary_search(t).map_or(f64::NAN, |pos| ts.values[pos]))
+                .collect();
+            (column_name(ts, i), values)
+        })
+        .collect();
+
+    build(index, columns, fill)
+}
+
+/// As-of join: for each timestamp of `left`, take the latest observation of
+/// each series in `right` at or before it, if no older than `tolerance`
+pub fn join_asof(
+    left: &TimeSeries,
+    right: &[TimeSeries],
+    tolerance: Duration,
+    fill: Option<FillMethod>,
+) -> Result<MultiTimeSeries> {
+    if tolerance < Duration::zero() {
+        return Err(TelemetryError::InvalidParameter(
+            "Tolerance must be non-negative".to_string(),
+        ));
+    }
+
+    let index = sorted_timestamps(left)?.to_vec();
+    let mut columns = vec![(column_name(left, 0), left.values.clone())];
+    for (i, ts) in right.iter().enumerate() {
+        let timestamps = sorted_timestamps(ts)?;
+        let values = index
+            .iter()
+            .map(|t| {
+                // Number of observations at or before t
+                let count = timestamps.partition_point(|x| x <= t);
+                match count.checked_sub(1) {
+                    Some(pos) if *t - timestamps[pos] <= tolerance => ts.values[pos],
+                    _ => f64::NAN,
+                }
+            })
+            .collect();
+        columns.push((column_name(ts, i + 1), values));
+    }
+
+    build(index, columns, fill)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn series(name: &str, minutes: &[i64], values: &[f64]) -> TimeSeries {
+        let start = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let timestamps = minutes.iter().map(|m| start + Duration::minutes(*m)).collect();
+        TimeSeries::with_timestamps(values.to_vec(), timestamps).unwrap().with_name(name)
+    }
+
+    #[test]
+    fn test_exact_joins() {
+        let station = series("station", &[0, 10, 20, 30], &[1.0, 2.0, 3.0, 4.0]);
+        let probe = series("probe", &[10, 15, 30], &[20.0, 25.0, 40.0]);
+        let both = [station, probe];
+
+        let inner = join(&both, JoinType::Inner, None).unwrap();
+        assert_eq!(inner.len(), 2);
+        assert_eq!(inner.values("probe").unwrap(), &[20.0, 40.0]);
+
+        let left = join(&both, JoinType::Left, None).unwrap();
+        assert_eq!(left.len(), 4);
+        assert!(left.values("probe").unwrap()[0].is_nan());
+
+        let outer = join(&both, JoinType::Outer, Some(FillMethod::Forward)).unwrap();
+        assert_eq!(outer.len(), 5);
+        // Minute 15 only exists in the probe; the station value is carried forward
+        assert_eq!(outer.values("station").unwrap()[2], 2.0);
+    }
+
+    #[test]
+    fn test_asof_join_with_tolerance() {
+        let satellite = series("satellite", &[0, 60, 120], &[0.1, 0.2, 0.3]);
+        let probe = series("probe", &[-5, 50, 58], &[7.0, 8.0, 9.0]);
+
+        let joined = join_asof(&satellite, &[probe], Duration::minutes(10), None).unwrap();
+        let values = joined.values("probe").unwrap();
+        assert_eq!(values[0], 7.0);
+        assert_eq!(values[1], 9.0);
+        // Latest probe reading is 62 minutes old
+        assert!(values[2].is_nan());
+    }
+
+    #[test]
+    fn test_join_requires_sorted_timestamps() {
+        let unsorted = series("a", &[10, 0], &[1.0, 2.0]);
+        assert!(join(&[unsorted], JoinType::Inner, None).is_err());
+        assert!(join(&[TimeSeries::new(vec![1.0])], JoinType::Outer, None).is_err());
+        assert!(join(&[], JoinType::Outer, None).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 //! Anomaly detection algorithms
 
+use crate::decomposition::DecompositionResult;
+use crate::time_series::quantile_sorted;
 use crate::{Result, TelemetryError, TimeSeries};
 
 /// Type of anomaly detected
@@ -27,6 +29,10 @@ pub struct Anomaly {
 }
 
 /// Anomaly detector using various statistical methods
+///
+/// Missing (`NaN`) values are never reported as anomalies and are excluded
+/// from the reference statistics; the minimum-length checks count only
+/// observed values.
 pub struct AnomalyDetector {
     /// Z-score threshold for detection
     pub z_threshold: f64,
@@ -54,7 +60,7 @@ impl AnomalyDetector {
 
     /// Detect anomalies using Z-score method
     pub fn detect_zscore(&self, ts: &TimeSeries) -> Result<Vec<Anomaly>> {
-        if ts.len() < 3 {
+        if ts.len() - ts.missing_count() < 3 {
             return Err(TelemetryError::InsufficientData(
                 "Need at least 3 data points for Z-score detection".to_string(),
             ));
@@ -66,7 +72,7 @@ impl AnomalyDetector {
         for (i, &value) in ts.values.iter().enumerate() {
             let z_score = ((value - stats.mean) / stats.std_dev).abs();
 
-            if z_score > self.z_threshold {
+            if !value.is_nan() && z_score > self.z_threshold {
                 anomalies.push(Anomaly {
                     index: i,
                     value,
@@ -81,13 +87,13 @@ impl AnomalyDetector {
 
     /// Detect anomalies using IQR (Interquartile Range) method
     pub fn detect_iqr(&self, ts: &TimeSeries) -> Result<Vec<Anomaly>> {
-        if ts.len() < 4 {
+        let mut sorted: Vec<f64> = ts.values.iter().cloned().filter(|v| !v.is_nan()).collect();
+        if sorted.len() < 4 {
             return Err(TelemetryError::InsufficientData(
                 "Need at least 4 data points for IQR detection".to_string(),
             ));
         }
 
-        let mut sorted = ts.values.clone();
         sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
         let n = sorted.len();
@@ -121,20 +127,22 @@ impl AnomalyDetector {
     }
 
     /// Detect anomalies using moving average deviation
+    ///
+    /// Points whose window contains a missing value are skipped.
     pub fn detect_moving_average(&self, ts: &TimeSeries, window: usize) -> Result<Vec<Anomaly>> {
         let ma = ts.moving_average(window)?;
         let mut anomalies = Vec::new();
 
         // Calculate deviations from moving average
         let offset = window / 2;
-        for i in 0..ma.len() {
+        for (i, &avg) in ma.iter().enumerate() {
             let actual_idx = i + offset;
             if actual_idx >= ts.values.len() {
                 break;
             }
 
-            let deviation = (ts.values[actual_idx] - ma[i]).abs();
-            let relative_dev = deviation / ma[i].abs().max(1e-10);
+            let deviation = (ts.values[actual_idx] - avg).abs();
+            let relative_dev = deviation / avg.abs().max(1e-10);
 
             if relative_dev > 0.5 {  // 50% deviation threshold
                 anomalies.push(Anomaly {
@@ -149,6 +157,49 @@ impl AnomalyDetector {
         Ok(anomalies)
     }
 
+    /// Detect anomalies in the residuals of a decomposition of `ts`
+    ///
+    /// Residuals are scored by their robust z-score (median and scaled
+    /// median absolute deviation) against `z_threshold`, so regular seasonal
+    /// swings are not flagged. Points with a missing residual are skipped.
+    pub fn detect_residuals(&self, ts: &TimeSeries, decomposition: &DecompositionResult) -> Result<Vec<Anomaly>> {
+        let residual = &decomposition.residual;
+        if residual.len() != ts.len() {
+            return Err(TelemetryError::InvalidData(
+                "Decomposition does not match the series length".to_string(),
+            ));
+        }
+
+        let mut sorted: Vec<f64> = residual.iter().copied().filter(|r| !r.is_nan()).collect();
+        if sorted.len() < 3 {
+            return Err(TelemetryError::InsufficientData(
+                "Need at least 3 residuals for residual detection".to_string(),
+            ));
+        }
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let median = quantile_sorted(&sorted, 0.5);
+        let mut deviations: Vec<f64> = sorted.iter().map(|r| (r - median).abs()).collect();
+        deviations.sort_by(|a, b| a.total_cmp(b));
+        let scale = 1.4826 * quantile_sorted(&deviations, 0.5);
+        if scale <= 0.0 {
+            return Ok(Vec::new());
+        }
+
+        Ok(residual
+            .iter()
+            .enumerate()
+            .filter(|(i, r)| !r.is_nan() && !ts.values[*i].is_nan())
+            .map(|(i, r)| (i, ((r - median) / scale).abs()))
+            .filter(|(_, score)| *score > self.z_threshold)
+            .map(|(index, score)| Anomaly {
+                index,
+                value: ts.values[index],
+                anomaly_type: AnomalyType::Contextual,
+                score,
+            })
+            .collect())
+    }
+
     /// Detect anomalies using multiple methods and aggregate results
     pub fn detect_ensemble(&self, ts: &TimeSeries) -> Result<Vec<Anomaly>> {
         let zscore_anomalies = self.detect_zscore(ts)?;
@@ -200,7 +251,12 @@ mod tests {
 
     #[test]
     fn test_zscore_detection() {
-        let data = vec![1.0, 2.0, 3.0, 2.0, 1.0, 100.0, 2.0, 1.0];
+        // With population std the largest attainable z-score is sqrt(n - 1),
+        // so the series must be long enough for a single spike to exceed 3.
+        let data = vec![
+            1.0, 2.0, 3.0, 2.0, 1.0, 100.0, 2.0, 1.0,
+            2.0, 3.0, 2.0, 1.0, 2.0, 3.0, 2.0, 1.0,
+        ];
         let ts = TimeSeries::new(data);
         let detector = AnomalyDetector::default();
 
@@ -218,4 +274,35 @@ mod tests {
         let anomalies = detector.detect_iqr(&ts).unwrap();
         assert!(!anomalies.is_empty());
     }
+
+    #[test]
+    fn test_detection_skips_missing() {
+        let mut data = vec![1.0, 2.0, 3.0, 2.0, 1.0, 100.0, 2.0, 1.0];
+        data[2] = f64::NAN;
+        let ts = TimeSeries::new(data);
+        let detector = AnomalyDetector::default();
+
+        let anomalies = detector.detect_ensemble(&ts).unwrap();
+        assert!(anomalies.iter().all(|a| !a.value.is_nan()));
+        assert!(anomalies.iter().any(|a| a.index == 5));
+    }
+
+    #[test]
+    fn test_residual_detection() {
+        // A dip to the seasonal trough level mid-peak is invisible to
+        // global methods but stands out in the residuals
+        let mut data: Vec<f64> = (0..96).map(|i| if i % 12 < 6 { 10.0 } else { 0.0 }).collect();
+        for (i, v) in data.iter_mut().enumerate() {
+            *v += 0.05 * ((i * 7) % 5) as f64;
+        }
+        data[62] = 0.5;
+        let ts = TimeSeries::new(data);
+        let detector = AnomalyDetector::default();
+        assert!(detector.detect_zscore(&ts).unwrap().is_empty());
+
+        let decomposition = crate::decomposition::STL::new(12).unwrap().robust().decompose(&ts).unwrap();
+        let anomalies = detector.detect_residuals(&ts, &decomposition).unwrap();
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].index, 62);
+    }
 }
@@ -0,0 +1,222 @@
+//! Rolling-origin evaluation (time series cross-validation) of forecasters
+//!
+//! Each fold fits the model on a training window and forecasts the next
+//! `horizon` observations. The window either grows from a fixed start
+//! (expanding) or keeps a fixed length (sliding); the forecast origin moves
+//! forward by `step` observations between folds.
+
+use crate::{Result, TelemetryError, TimeSeries};
+use crate::forecasting::Forecaster;
+use crate::metrics;
+
+/// How the training window evolves between folds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowType {
+    /// Training data always starts at the first observation
+    Expanding,
+    /// Training data keeps the initial length and moves with the origin
+    Sliding,
+}
+
+/// Point forecast accuracy over a set of forecast/actual pairs
+///
+/// Pairs with a missing value are skipped; MAPE also skips zero actuals.
+/// See [`crate::metrics`] for further scores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorMetrics {
+    /// Mean absolute error
+    pub mae: f64,
+    /// Root mean squared error
+    pub rmse: f64,
+    /// Mean absolute percentage error, in percent
+    pub mape: f64,
+    /// Number of pairs evaluated
+    pub count: usize,
+}
+
+impl ErrorMetrics {
+    /// Compute the metrics from aligned actual and forecast values
+    ///
+    /// Metrics that cannot be computed (e.g. MAPE with all-zero actuals)
+    /// are `NaN`.
+    pub fn compute(actual: &[f64], forecast: &[f64]) -> Self {
+        let actual = &actual[..actual.len().min(forecast.len())];
+        let forecast = &forecast[..actual.len()];
+        Self {
+            mae: metrics::mae(actual, forecast).unwrap_or(f64::NAN),
+            rmse: metrics::rmse(actual, forecast).unwrap_or(f64::NAN),
+            mape: metrics::mape(actual, forecast).unwrap_or(f64::NAN),
+            count: actual.iter().zip(forecast).filter(|(a, f)| !a.is_nan() && !f.is_nan()).count(),
+        }
+    }
+}
+
+/// Outcome of a single fold
+#[derive(Debug, Clone)]
+pub struct FoldResult {
+    /// Index of the first training observation
+    pub train_start: usize,
+    /// One past the last training observation (the forecast origin)
+    pub train_end: usize,
+    /// Point forecasts for the test window
+    pub forecast: Vec<f64>,
+    /// Observed values for the test window
+    pub actual: Vec<f64>,
+    /// Accuracy of this fold
+    pub metrics: ErrorMetrics,
+}
+
+/// Per-fold and aggregate results of a backtest
+#[derive(Debug, Clone)]
+pub struct BacktestResult {
+    /// Results in order of forecast origin
+    pub folds: Vec<FoldResult>,
+    /// Accuracy pooled over all folds and horizons
+    pub aggregate: ErrorMetrics,
+    /// Accuracy pooled over folds for each horizon 1..=h
+    pub by_horizon: Vec<ErrorMetrics>,
+}
+
+/// Rolling-origin backtest configuration
+#[derive(Debug, Clone)]
+pub struct Backtest {
+    window: WindowType,
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+}
+
+impl Backtest {
+    /// Expanding-window backtest starting with `initial_train` observations
+    pub fn expanding(initial_train: usize, horizon: usize) -> Result<Self> {
+        Self::new(WindowType::Expanding, initial_train, horizon)
+    }
+
+    /// Sliding-window backtest with a training window of `train_size`
+    pub fn sliding(train_size: usize, horizon: usize) -> Result<Self> {
+        Self::new(WindowType::Sliding, train_size, horizon)
+    }
+
+    fn new(window: WindowType, initial_train: usize, horizon: usize) -> Result<Self> {
+        if initial_train == 0 || horizon == 0 {
+            return Err(TelemetryError::InvalidParameter(
+                "Training size and horizon must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self { window, initial_train, horizon, step: 1 })
+    }
+
+    /// Move the forecast origin by `step` observations between folds (default 1)
+    pub fn with_step(mut self, step: usize) -> Result<Self> {
+        if step == 0 {
+            return Err(TelemetryError::InvalidParameter(
+                "Step must be positive".to_string(),
+            ));
+        }
+        self.step = step;
+        Ok(self)
+    }
+
+    /// Window type
+    pub fn window(&self) -> WindowType {
+        self.window
+    }
+
+    /// Training and test index ranges `(train_start, train_end, test_end)`
+    /// for a series of length `n`; only folds with a complete test window
+    /// are produced
+    pub fn splits(&self, n: usize) -> Vec<(usize, usize, usize)> {
+        let mut splits = Vec::new();
+        let mut origin = self.initial_train;
+        while origin + self.horizon <= n {
+            let start = match self.window {
+                WindowType::Expanding => 0,
+                WindowType::Sliding => origin - self.initial_train,
+            };
+            splits.push((start, origin, origin + self.horizon));
+            origin += self.step;
+        }
+        splits
+    }
+
+    /// Fit and forecast `model` on every fold of `ts`
+    ///
+    /// The model is refitted from scratch for each fold, so its state after
+    /// the call reflects the last fold.
+    pub fn run<F: Forecaster + ?Sized>(&self, model: &mut F, ts: &TimeSeries) -> Result<BacktestResult> {
+        let splits = self.splits(ts.len());
+        if splits.is_empty() {
+            return Err(TelemetryError::InsufficientData(format!(
+                "Need at least {} data points for one fold",
+                self.initial_train + self.horizon
+            )));
+        }
+
+        let mut folds = Vec::with_capacity(splits.len());
+        for (train_start, train_end, test_end) in splits {
+            model.fit(&ts.slice(train_start, train_end)?)?;
+            let forecast = model.forecast(self.horizon)?.predictions;
+            let actual = ts.values[train_end..test_end].to_vec();
+            let metrics = ErrorMetrics::compute(&actual, &forecast);
+            folds.push(FoldResult { train_start, train_end, forecast, actual, metrics });
+        }
+
+        let pooled = |horizons: std::ops::Range<usize>| {
+            let mut actual = Vec::new();
+            let mut forecast = Vec::new();
+            for fold in &folds {
+                actual.extend_from_slice(&fold.actual[horizons.clone()]);
+                forecast.extend_from_slice(&fold.forecast[horizons.clone()]);
+            }
+            ErrorMetrics::compute(&actual, &forecast)
+        };
+        let aggregate = pooled(0..self.horizon);
+        let by_horizon = (0..self.horizon).map(|h| pooled(h..h + 1)).collect();
+
+        Ok(BacktestResult { folds, aggregate, by_horizon })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecasting::{Holt, MovingAverageForecaster};
+
+    #[test]
+    fn test_expanding_and_sliding_splits() {
+        let expanding = Backtest::expanding(5, 2).unwrap().with_step(2).unwrap();
+        assert_eq!(expanding.splits(10), vec![(0, 5, 7), (0, 7, 9)]);
+
+        let sliding = Backtest::sliding(5, 2).unwrap();
+        let splits = sliding.splits(8);
+        assert_eq!(splits, vec![(0, 5, 7), (1, 6, 8)]);
+
+        assert!(Backtest::expanding(5, 0).is_err());
+        assert!(Backtest::expanding(5, 1).unwrap().with_step(0).is_err());
+    }
+
+    #[test]
+    fn test_backtest_linear_trend() {
+        let ts = TimeSeries::new((0..40).map(|x| 2.0 * x as f64).collect());
+
+        let mut holt = Holt::new();
+        let result = Backtest::expanding(20, 3).unwrap().run(&mut holt, &ts).unwrap();
+        assert_eq!(result.folds.len(), 18);
+        assert!(result.aggregate.mae < 1e-2);
+
+        // A moving average lags a trend by (window + 1) / 2 steps per horizon
+        let mut ma = MovingAverageForecaster::new(3).unwrap();
+        let result = Backtest::sliding(10, 2).unwrap().run(&mut ma, &ts).unwrap();
+        assert!((result.by_horizon[0].mae - 4.0).abs() < 1e-9);
+        assert!(result.by_horizon[1].mae > result.by_horizon[0].mae);
+        assert_eq!(result.aggregate.count, 2 * result.folds.len());
+    }
+
+    #[test]
+    fn test_backtest_too_short() {
+        let ts = TimeSeries::new(vec![1.0, 2.0, 3.0]);
+        let mut ma = MovingAverageForecaster::new(2).unwrap();
+        assert!(Backtest::expanding(3, 1).unwrap().run(&mut ma, &ts).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+//! Autocorrelation, partial autocorrelation and cross-correlation functions
+//!
+//! Correlations use the biased (divide-by-n) covariance estimates, so the
+//! ACF is positive semi-definite and the PACF follows from it by the
+//! Durbin-Levinson recursion. With the `fft` feature, long lag ranges are
+//! computed in O(n log n) through the FFT instead of O(n · max_lag).
+
+use crate::forecasting::z_score;
+use crate::models::arima::{autocovariance, levinson_durbin};
+use crate::{Result, TelemetryError, TimeSeries};
+
+/// Lags up to which correlations are summed directly rather than by FFT
+#[cfg(feature = "fft")]
+const DIRECT_MAX_LAG: usize = 16;
+
+/// Correlation coefficients by lag with confidence bands around zero
+#[derive(Debug, Clone)]
+pub struct Correlogram {
+    /// Lag of each coefficient
+    pub lags: Vec<isize>,
+    /// Correlation coefficients
+    pub values: Vec<f64>,
+    /// Confidence level of the bands
+    pub confidence: f64,
+    /// Half-width `z / sqrt(n)` of the band for white noise
+    pub white_noise_band: f64,
+    /// Half-width of Bartlett's band at each lag, which accounts for the
+    /// correlation at lower lags (ACF) or within each series (CCF); equal to
+    /// the white-noise band for the PACF
+    pub bartlett_bands: Vec<f64>,
+}
+
+impl Correlogram {
+    /// Coefficient at `lag`, if computed
+    pub fn at(&self, lag: isize) -> Option<f64> {
+        self.lags.iter().position(|&l| l == lag).map(|i| self.values[i])
+    }
+
+    /// Lags whose coefficient lies outside the Bartlett band
+    pub fn significant_lags(&self) -> Vec<isize> {
+        self.lags
+            .iter()
+            .zip(self.values.iter().zip(&self.bartlett_bands))
+            .filter(|(_, (v, band))| v.abs() > **band)
+            .map(|(&lag, _)| lag)
+            .collect()
+    }
+
+    /// Lag with the largest absolute coefficient
+    pub fn strongest(&self) -> Option<(isize, f64)> {
+        self.lags
+            .iter()
+            .zip(&self.values)
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .map(|(&lag, &value)| (lag, value))
+    }
+}
+
+/// Demeaned values of a series without missing values and with variance
+fn centred(ts: &TimeSeries) -> Result<Vec<f64>> {
+    if ts.values.iter().any(|v| !v.is_finite()) {
+        return Err(TelemetryError::InvalidData(
+            "Correlation functions require finite values; fill missing values first".to_string(),
+        ));
+    }
+
+    let mean = ts.values.iter().sum::<f64>() / ts.len() as f64;
+    let x: Vec<f64> = ts.values.iter().map(|v| v - mean).collect();
+    if x.iter().all(|v| *v == 0.0) {
+        return Err(TelemetryError::InvalidData(
+            "Series is constant".to_string(),
+        ));
+    }
+    Ok(x)
+}
+
+fn check_max_lag(max_lag: usize, n: usize) -> Result<()> {
+    if max_lag == 0 || max_lag >= n {
+        return Err(TelemetryError::InvalidParameter(
+            format!("Maximum lag must be between 1 and {}", n.saturating_sub(1)),
+        ));
+    }
+    Ok(())
+}
+
+/// Autocorrelations at lags `0..=max_lag` of a centred series
+fn autocorrelations(x: &[f64], max_lag: usize) -> Vec<f64> {
+    #[cfg(feature = "fft")]
+    if max_lag > DIRECT_MAX_LAG {
+        return crate::spectral::autocorrelation_fft(x, max_lag);
+    }
+
+    let gamma = autocovariance(x, max_lag);
+    gamma.iter().map(|g| g / gamma[0]).collect()
+}
+
+/// Cross-covariances `sum_t x[t] * y[t + k] / n` for `k = -max_lag..=max_lag`
+fn cross_covariances(x: &[f64], y: &[f64], max_lag: usize) -> Vec<f64> {
+    #[cfg(feature = "fft")]
+    if max_lag > DIRECT_MAX_LAG {
+        return crate::spectral::cross_covariance_fft(x, y, max_lag);
+    }
+
+    let n = x.len();
+    (-(max_lag as isize)..=max_lag as isize)
+        .map(|k| {
+            let (a, b) = if k >= 0 { (x, &y[k as usize..]) } else { (&x[k.unsigned_abs()..], y) };
+            a.iter().zip(b).map(|(u, v)| u * v).sum::<f64>() / n as f64
+        })
+        .collect()
+}
+
+/// Autocorrelation function at lags `1..=max_lag`
+///
+/// Bartlett's band at lag k is `z * sqrt((1 + 2 * sum_{j<k} r_j^2) / n)`,
+/// the band for an MA(k-1) process; use it to read off MA orders.
+pub fn acf(ts: &TimeSeries, max_lag: usize, confidence: f64) -> Result<Correlogram> {
+    check_max_lag(max_lag, ts.len())?;
+    let z = z_score(confidence)?;
+    let r = autocorrelations(&centred(ts)?, max_lag);
+    let n = ts.len() as f64;
+
+    let mut cumulative = 0.0;
+    let bartlett_bands = (1..=max_lag)
+        .map(|k| {
+            let band = z * ((1.0 + 2.0 * cumulative) / n).sqrt();
+            cumulative += r[k] * r[k];
+            band
+        })
+        .collect();
+
+    Ok(Correlogram {
+        lags: (1..=max_lag as isize).collect(),
+        values: r[1..].to_vec(),
+        confidence,
+        white_noise_band: z / n.sqrt(),
+        bartlett_bands,
+    })
+}
+
+/// Partial autocorrelation function at lags `1..=max_lag`, by the
+/// Durbin-Levinson recursion on the sample autocorrelations
+///
+/// Under an AR(p) process the coefficients beyond lag p have standard error
+/// `1 / sqrt(n)`, so both bands are the white-noise band.
+pub fn pacf(ts: &TimeSeries, max_lag: usize, confidence: f64) -> Result<Correlogram> {
+    check_max_lag(max_lag, ts.len())?;
+    let z = z_score(confidence)?;
+    let r = autocorrelations(&centred(ts)?, max_lag);
+    let band = z / (ts.len() as f64).sqrt();
+
+    Ok(Correlogram {
+        lags: (1..=max_lag as isize).collect(),
+        values: levinson_durbin(&r, max_lag).1,
+        confidence,
+        white_noise_band: band,
+        bartlett_bands: vec![band; max_lag],
+    })
+}
+
+/// Cross-correlation `corr(x[t], y[t + k])` for `k = -max_lag..=max_lag`
+///
+/// A peak at a positive lag k means `x` leads `y` by k steps (e.g. rainfall
+/// leading soil moisture). The series must be aligned and of equal length.
+/// Bartlett's band under independence is
+/// `z * sqrt((1 + 2 * sum_j r_xx(j) * r_yy(j)) / n)`, which widens when both
+/// series are autocorrelated; prewhitening both series sharpens the peak.
+pub fn ccf(x: &TimeSeries, y: &TimeSeries, max_lag: usize, confidence: f64) -> Result<Correlogram> {
+    if x.len() != y.len() {
+        return Err(TelemetryError::InvalidData(
+            "Series must have the same length".to_string(),
+        ));
+    }
+    check_max_lag(max_lag, x.len())?;
+    let z = z_score(confidence)?;
+    let (xc, yc) = (centred(x)?, centred(y)?);
+    let n = x.len() as f64;
+
+    let scale = (autocovariance(&xc, 0)[0] * autocovariance(&yc, 0)[0]).sqrt();
+    let values = cross_covariances(&xc, &yc, max_lag).into_iter().map(|c| c / scale).collect();
+
+    let (rx, ry) = (autocorrelations(&xc, max_lag), autocorrelations(&yc, max_lag));
+    let shared: f64 = (1..=max_lag).map(|j| rx[j] * ry[j]).sum();
+    let bartlett = z * ((1.0 + 2.0 * shared).max(0.0) / n).sqrt();
+
+    Ok(Correlogram {
+        lags: (-(max_lag as isize)..=max_lag as isize).collect(),
+        values,
+        confidence,
+        white_noise_band: z / n.sqrt(),
+        bartlett_bands: vec![bartlett; 2 * max_lag + 1],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::shocks;
+
+    fn ar1(phi: f64, n: usize) -> Vec<f64> {
+        let e = shocks(n);
+        let mut x = vec![e[0]];
+        for t in 1..n {
+            x.push(phi * x[t - 1] + e[t]);
+        }
+        x
+    }
+
+    #[test]
+    fn test_acf_and_pacf_of_ar1() {
+        let ts = TimeSeries::new(ar1(0.7, 500));
+        let correlogram = acf(&ts, 10, 0.95).unwrap();
+        assert!((correlogram.at(1).unwrap() - 0.7).abs() < 0.08);
+        assert!((correlogram.white_noise_band - 1.96 / 500f64.sqrt()).abs() < 1e-3);
+        assert!(correlogram.bartlett_bands.windows(2).all(|w| w[1] >= w[0]));
+
+        let partial = pacf(&ts, 10, 0.95).unwrap();
+        assert!((partial.at(1).unwrap() - correlogram.at(1).unwrap()).abs() < 1e-12);
+        assert_eq!(partial.significant_lags().first(), Some(&1));
+        assert!(partial.at(2).unwrap().abs() < partial.white_noise_band);
+    }
+
+    #[test]
+    fn test_fft_and_direct_paths_agree() {
+        let x = TimeSeries::new(ar1(0.5, 300));
+        let y = TimeSeries::new(ar1(-0.3, 300));
+
+        let short = acf(&x, 10, 0.95).unwrap();
+        let long = acf(&x, 60, 0.95).unwrap();
+        for lag in 1..=10 {
+            assert!((short.at(lag).unwrap() - long.at(lag).unwrap()).abs() < 1e-10);
+        }
+
+        let short = ccf(&x, &y, 5, 0.95).unwrap();
+        let long = ccf(&x, &y, 40, 0.95).unwrap();
+        for lag in -5..=5 {
+            assert!((short.at(lag).unwrap() - long.at(lag).unwrap()).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_ccf_finds_lead() {
+        let draws = shocks(800);
+        let (rain, noise) = draws.split_at(400);
+        let rain = rain.to_vec();
+        let moisture: Vec<f64> = (0..400)
+            .map(|t| if t >= 3 { 0.8 * rain[t - 3] } else { 0.0 } + 0.3 * noise[t])
+            .collect();
+
+        let cross = ccf(&TimeSeries::new(rain.clone()), &TimeSeries::new(moisture), 10, 0.95).unwrap();
+        let (lag, value) = cross.strongest().unwrap();
+        assert_eq!(lag, 3);
+        assert!(value > 0.8);
+        assert!(cross.significant_lags().contains(&3));
+
+        let short = TimeSeries::new(rain[..100].to_vec());
+        assert!(ccf(&short, &TimeSeries::new(rain), 5, 0.95).is_err());
+        assert!(acf(&TimeSeries::new(vec![2.0; 10]), 3, 0.95).is_err());
+        assert!(acf(&short, 100, 0.95).is_err());
+    }
+}
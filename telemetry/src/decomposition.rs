@@ -1,16 +1,34 @@
 //! Time series decomposition (trend, seasonality, residuals)
 
+pub mod stl;
+pub mod mstl;
+
 use crate::{Result, TelemetryError, TimeSeries};
 
+pub use stl::STL;
+pub use mstl::{MSTLForecaster, MSTL};
+
 /// Result of time series decomposition
 #[derive(Debug, Clone)]
 pub struct DecompositionResult {
     /// Trend component
     pub trend: Vec<f64>,
-    /// Seasonal component
+    /// Seasonal component (the sum of all seasonal components)
     pub seasonal: Vec<f64>,
     /// Residual component
     pub residual: Vec<f64>,
+    /// `(period, component)` pairs, one per seasonal period
+    pub seasonal_components: Vec<(usize, Vec<f64>)>,
+}
+
+impl DecompositionResult {
+    /// Seasonal component for `period`, if it was estimated
+    pub fn seasonal_component(&self, period: usize) -> Option<&[f64]> {
+        self.seasonal_components
+            .iter()
+            .find(|(p, _)| *p == period)
+            .map(|(_, values)| values.as_slice())
+    }
 }
 
 /// Type of decomposition
@@ -65,12 +83,16 @@ impl Decomposer {
 
         Ok(DecompositionResult {
             trend,
+            seasonal_components: vec![(self.period, seasonal.clone())],
             seasonal,
             residual,
         })
     }
 
-    /// Calculate trend component using moving average
+    /// Calculate trend component using a centered moving average
+    ///
+    /// Even periods use a 2×m moving average (half weights at both ends) so
+    /// that every season position gets the same total weight.
     fn calculate_trend(&self, ts: &TimeSeries) -> Result<Vec<f64>> {
         let mut trend = Vec::with_capacity(ts.len());
         let half_window = self.period / 2;
@@ -82,7 +104,11 @@ impl Decomposer {
 
         // Calculate centered moving average
         for i in half_window..(ts.len() - half_window) {
-            let sum: f64 = ts.values[i - half_window..=i + half_window].iter().sum();
+            let window = &ts.values[i - half_window..=i + half_window];
+            let mut sum: f64 = window.iter().sum();
+            if self.period.is_multiple_of(2) {
+                sum -= 0.5 * (window[0] + window[window.len() - 1]);
+            }
             trend.push(sum / self.period as f64);
         }
 
@@ -211,5 +237,9 @@ mod tests {
         assert_eq!(result.trend.len(), ts.len());
         assert_eq!(result.seasonal.len(), ts.len());
         assert_eq!(result.residual.len(), ts.len());
+
+        // The 2x4 moving average recovers the linear trend exactly
+        assert!((result.trend[5] - 6.5).abs() < 1e-12);
+        assert!((result.seasonal[3] - 1.5).abs() < 1e-12);
     }
 }
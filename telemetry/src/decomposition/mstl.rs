@@ -0,0 +1,284 @@
+//! MSTL: decomposition with several seasonal periods
+//!
+//! Follows Bandara, Hyndman & Bergmeir (2021): seasonal components are
+//! extracted one period at a time, shortest first, by repeated STL fits on
+//! the series with the other components removed. The decomposition also
+//! drives [`MSTLForecaster`], which forecasts the seasonally adjusted series
+//! with any [`Forecaster`] and adds the seasonal cycles back.
+
+use crate::{Result, TelemetryError, TimeSeries};
+use crate::forecasting::{ForecastResult, Forecaster};
+use super::{DecompositionResult, STL};
+
+/// Additive decomposition into a trend, one seasonal component per period
+/// and a residual
+#[derive(Debug, Clone)]
+pub struct MSTL {
+    periods: Vec<usize>,
+    seasonal_windows: Vec<usize>,
+    iterations: usize,
+    robust: bool,
+}
+
+impl MSTL {
+    /// MSTL for the given periods (e.g. `[24, 168]` for hourly data)
+    ///
+    /// Seasonal windows default to `7 + 4 * i` for the i-th shortest period.
+    pub fn new(periods: &[usize]) -> Result<Self> {
+        if periods.is_empty() {
+            return Err(TelemetryError::InvalidParameter(
+                "Need at least one seasonal period".to_string(),
+            ));
+        }
+        if periods.iter().any(|&p| p < 2) {
+            return Err(TelemetryError::InvalidParameter(
+                "Periods must be at least 2".to_string(),
+            ));
+        }
+
+        let mut sorted = periods.to_vec();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|w| w[0] == w[1]) {
+            return Err(TelemetryError::InvalidParameter(
+                "Periods must be distinct".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            seasonal_windows: (1..=sorted.len()).map(|i| 7 + 4 * i).collect(),
+            periods: sorted,
+            iterations: 2,
+            robust: false,
+        })
+    }
+
+    /// Seasonal windows, one per period in ascending order of period
+    pub fn with_seasonal_windows(mut self, windows: &[usize]) -> Result<Self> {
+        if windows.len() != self.periods.len() {
+            return Err(TelemetryError::InvalidParameter(
+                "Need one seasonal window per period".to_string(),
+            ));
+        }
+        for &window in windows {
+            STL::new(2)?.with_seasonal_window(window)?;
+        }
+        self.seasonal_windows = windows.to_vec();
+        Ok(self)
+    }
+
+    /// Number of passes over all periods (default 2)
+    pub fn with_iterations(mut self, iterations: usize) -> Result<Self> {
+        if iterations == 0 {
+            return Err(TelemetryError::InvalidParameter(
+                "Need at least one iteration".to_string(),
+            ));
+        }
+        self.iterations = iterations;
+        Ok(self)
+    }
+
+    /// Use robust STL fits that downweight outliers
+    pub fn robust(mut self) -> Self {
+        self.robust = true;
+        self
+    }
+
+    /// Seasonal periods in ascending order
+    pub fn periods(&self) -> &[usize] {
+        &self.periods
+    }
+
+    /// Decompose a series without missing values
+    ///
+    /// `seasonal` holds the sum of the components in `seasonal_components`.
+    pub fn decompose(&self, ts: &TimeSeries) -> Result<DecompositionResult> {
+        let longest = *self.periods.last().unwrap();
+        if ts.len() < 2 * longest {
+            return Err(TelemetryError::InsufficientData(
+                format!("Need at least {} data points for period {}", 2 * longest, longest),
+            ));
+        }
+
+        let stls = self
+            .periods
+            .iter()
+            .zip(&self.seasonal_windows)
+            .map(|(&period, &window)| {
+                let stl = STL::new(period)?.with_seasonal_window(window)?;
+                Ok(if self.robust { stl.robust() } else { stl })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let n = ts.len();
+        let mut adjusted = ts.values.clone();
+        let mut components = vec![vec![0.0; n]; stls.len()];
+        let mut trend = vec![0.0; n];
+
+        for _ in 0..self.iterations {
+            for (stl, component) in stls.iter().zip(components.iter_mut()) {
+                adjusted.iter_mut().zip(component.iter()).for_each(|(a, s)| *a += s);
+                let fit = stl.decompose(&TimeSeries::new(adjusted.clone()))?;
+                adjusted.iter_mut().zip(&fit.seasonal).for_each(|(a, s)| *a -= s);
+                *component = fit.seasonal;
+                trend = fit.trend;
+            }
+        }
+
+        let seasonal: Vec<f64> = (0..n).map(|t| components.iter().map(|c| c[t]).sum()).collect();
+        let residual = (0..n).map(|t| ts.values[t] - trend[t] - seasonal[t]).collect();
+
+        Ok(DecompositionResult {
+            trend,
+            seasonal,
+            residual,
+            seasonal_components: self.periods.iter().copied().zip(components).collect(),
+        })
+    }
+}
+
+/// Forecaster for multi-seasonal series
+///
+/// The series is decomposed with [`MSTL`]; the inner model forecasts the
+/// seasonally adjusted series (trend plus residual), and every seasonal
+/// component is continued by repeating its last cycle. Prediction intervals
+/// come from the inner model, shifted by the seasonal terms.
+pub struct MSTLForecaster<F: Forecaster> {
+    decomposer: MSTL,
+    model: F,
+    fit: Option<DecompositionResult>,
+}
+
+impl<F: Forecaster> MSTLForecaster<F> {
+    /// Combine a decomposer with a non-seasonal forecaster for the adjusted series
+    pub fn new(decomposer: MSTL, model: F) -> Self {
+        Self {
+            decomposer,
+            model,
+            fit: None,
+        }
+    }
+
+    /// Model fitted to the seasonally adjusted series
+    pub fn model(&self) -> &F {
+        &self.model
+    }
+
+    /// Decomposition of the fitted series
+    pub fn decomposition(&self) -> Option<&DecompositionResult> {
+        self.fit.as_ref()
+    }
+
+    fn reseasonalise(&self, mut result: ForecastResult) -> Result<ForecastResult> {
+        let fit = self.fit.as_ref().ok_or_else(|| {
+            TelemetryError::ModelError("Model not fitted yet".to_string())
+        })?;
+        let n = fit.trend.len();
+        let seasonal: Vec<f64> = (0..result.predictions.len())
+            .map(|h| {
+                fit.seasonal_components
+                    .iter()
+                    .map(|(period, c)| c[n - period + h % period])
+                    .sum()
+            })
+            .collect();
+
+        let shift = |values: &mut Vec<f64>| values.iter_mut().zip(&seasonal).for_each(|(v, s)| *v += s);
+        shift(&mut result.predictions);
+        if let Some(lower) = result.lower_bound.as_mut() {
+            shift(lower);
+        }
+        if let Some(upper) = result.upper_bound.as_mut() {
+            shift(upper);
+        }
+        for interval in &mut result.intervals {
+            shift(&mut interval.lower);
+            shift(&mut interval.upper);
+        }
+        Ok(result)
+    }
+}
+
+impl<F: Forecaster> Forecaster for MSTLForecaster<F> {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        let decomposition = self.decomposer.decompose(ts)?;
+        let adjusted = TimeSeries {
+            values: ts.values.iter().zip(&decomposition.seasonal).map(|(v, s)| v - s).collect(),
+            timestamps: ts.timestamps.clone(),
+            name: ts.name.clone(),
+        };
+        self.model.fit(&adjusted)?;
+        self.fit = Some(decomposition);
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        self.reseasonalise(self.model.forecast(steps)?)
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        self.reseasonalise(self.model.forecast_with_confidence(steps, confidence_level)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecasting::Drift;
+    use std::f64::consts::PI;
+
+    /// Hourly-style series with daily (24) and weekly (168) cycles
+    fn two_cycles(n: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let daily: Vec<f64> = (0..n).map(|t| 3.0 * (2.0 * PI * t as f64 / 24.0).sin()).collect();
+        let weekly: Vec<f64> = (0..n).map(|t| 5.0 * (2.0 * PI * t as f64 / 168.0).cos()).collect();
+        let values = (0..n).map(|t| 50.0 + 0.01 * t as f64 + daily[t] + weekly[t]).collect();
+        (values, daily, weekly)
+    }
+
+    #[test]
+    fn test_mstl_separates_cycles() {
+        let (values, daily, weekly) = two_cycles(168 * 4);
+        let ts = TimeSeries::new(values.clone());
+        let result = MSTL::new(&[168, 24]).unwrap().decompose(&ts).unwrap();
+
+        assert_eq!(result.seasonal_components.len(), 2);
+        let fitted_daily = result.seasonal_component(24).unwrap();
+        let fitted_weekly = result.seasonal_component(168).unwrap();
+        let error = |fitted: &[f64], truth: &[f64]| {
+            (168..3 * 168).map(|t| (fitted[t] - truth[t]).abs()).fold(0.0, f64::max)
+        };
+        assert!(error(fitted_daily, &daily) < 0.3);
+        assert!(error(fitted_weekly, &weekly) < 0.6);
+
+        for (t, value) in values.iter().enumerate() {
+            let total = result.trend[t] + result.seasonal[t] + result.residual[t];
+            assert!((total - value).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_mstl_forecaster() {
+        let (values, _, _) = two_cycles(168 * 4 + 48);
+        let (train, test) = values.split_at(168 * 4);
+
+        let mut model = MSTLForecaster::new(MSTL::new(&[24, 168]).unwrap(), Drift::new());
+        model.fit(&TimeSeries::new(train.to_vec())).unwrap();
+        let forecast = model.forecast_with_confidence(48, 0.9).unwrap();
+
+        let mae = forecast.predictions.iter().zip(test).map(|(f, a)| (f - a).abs()).sum::<f64>() / 48.0;
+        assert!(mae < 1.0);
+        let lower = forecast.lower_bound.as_ref().unwrap();
+        assert!(lower.iter().zip(&forecast.predictions).all(|(l, p)| l < p));
+        assert_eq!(model.decomposition().unwrap().seasonal_components.len(), 2);
+    }
+
+    #[test]
+    fn test_mstl_parameters() {
+        assert!(MSTL::new(&[]).is_err());
+        assert!(MSTL::new(&[24, 24]).is_err());
+        assert!(MSTL::new(&[1, 24]).is_err());
+        assert!(MSTL::new(&[24, 168]).unwrap().with_seasonal_windows(&[11]).is_err());
+        assert!(MSTL::new(&[24, 168]).unwrap().with_seasonal_windows(&[11, 14]).is_err());
+        assert!(MSTL::new(&[24, 168]).unwrap().decompose(&TimeSeries::new(vec![1.0; 300])).is_err());
+        assert_eq!(MSTL::new(&[168, 24]).unwrap().periods(), &[24, 168]);
+    }
+}
@@ -0,0 +1,372 @@
+//! STL: Seasonal-Trend decomposition using Loess
+//!
+//! Implements the algorithm of Cleveland, Cleveland, McRae & Terpenning
+//! (1990). Each inner pass smooths the cycle-subseries of the detrended
+//! series with loess, removes their low-frequency part, and re-estimates the
+//! trend from the deseasonalised series. Outer passes compute bisquare
+//! robustness weights from the remainder so that outliers stop pulling the
+//! trend and seasonal estimates. Unlike [`Decomposer`](super::Decomposer),
+//! the seasonal pattern may evolve over time and every component covers the
+//! full length of the series.
+
+use crate::time_series::quantile_sorted;
+use crate::{Result, TelemetryError, TimeSeries};
+use super::DecompositionResult;
+
+/// Degree of the local polynomials (as in R's `stl` defaults)
+const SEASONAL_DEGREE: usize = 0;
+const TREND_DEGREE: usize = 1;
+const LOW_PASS_DEGREE: usize = 1;
+
+/// Additive STL decomposition
+///
+/// Multiplicative series can be decomposed on the log scale.
+#[derive(Debug, Clone)]
+pub struct STL {
+    period: usize,
+    seasonal_window: usize,
+    trend_window: Option<usize>,
+    robust: bool,
+    inner_iterations: Option<usize>,
+    outer_iterations: Option<usize>,
+}
+
+impl STL {
+    /// STL with a seasonal window of 7 and the default trend window
+    pub fn new(period: usize) -> Result<Self> {
+        if period < 2 {
+            return Err(TelemetryError::InvalidParameter(
+                "Period must be at least 2".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            period,
+            seasonal_window: 7,
+            trend_window: None,
+            robust: false,
+            inner_iterations: None,
+            outer_iterations: None,
+        })
+    }
+
+    /// Loess window, in periods, for the cycle-subseries (odd, at least 3);
+    /// larger windows give a more stable seasonal pattern
+    pub fn with_seasonal_window(mut self, window: usize) -> Result<Self> {
+        self.seasonal_window = check_window(window, "Seasonal")?;
+        Ok(self)
+    }
+
+    /// Loess window, in observations, for the trend (odd, at least 3)
+    ///
+    /// Defaults to the smallest odd integer not below
+    /// `1.5 * period / (1 - 1.5 / seasonal_window)`.
+    pub fn with_trend_window(mut self, window: usize) -> Result<Self> {
+        self.trend_window = Some(check_window(window, "Trend")?);
+        Ok(self)
+    }
+
+    /// Downweight outliers with bisquare robustness iterations
+    pub fn robust(mut self) -> Self {
+        self.robust = true;
+        self
+    }
+
+    /// Number of inner (smoothing) and outer (robustness) passes
+    ///
+    /// Defaults to 2 inner and 0 outer passes, or 1 and 15 when robust.
+    pub fn with_iterations(mut self, inner: usize, outer: usize) -> Result<Self> {
+        if inner == 0 {
+            return Err(TelemetryError::InvalidParameter(
+                "Need at least one inner iteration".to_string(),
+            ));
+        }
+        self.inner_iterations = Some(inner);
+        self.outer_iterations = Some(outer);
+        Ok(self)
+    }
+
+    /// Seasonal period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    fn trend_window(&self) -> usize {
+        self.trend_window.unwrap_or_else(|| {
+            let ns = self.seasonal_window as f64;
+            next_odd((1.5 * self.period as f64 / (1.0 - 1.5 / ns)).ceil() as usize)
+        })
+    }
+
+    /// Decompose a series without missing values into full-length trend,
+    /// seasonal and residual components
+    pub fn decompose(&self, ts: &TimeSeries) -> Result<DecompositionResult> {
+        if ts.len() < 2 * self.period {
+            return Err(TelemetryError::InsufficientData(
+                format!("Need at least {} data points for period {}", 2 * self.period, self.period),
+            ));
+        }
+        if ts.values.iter().any(|v| !v.is_finite()) {
+            return Err(TelemetryError::InvalidData(
+                "STL requires finite values; fill missing values first".to_string(),
+            ));
+        }
+
+        let y = &ts.values;
+        let n = y.len();
+        let inner = self.inner_iterations.unwrap_or(if self.robust { 1 } else { 2 });
+        let outer = self.outer_iterations.unwrap_or(if self.robust { 15 } else { 0 });
+
+        let mut trend = vec![0.0; n];
+        let mut seasonal = vec![0.0; n];
+        let mut weights = vec![1.0; n];
+
+        for iteration in 0..=outer {
+            for _ in 0..inner {
+                seasonal = self.seasonal_pass(y, &trend, &weights);
+                let deseasonalised: Vec<f64> = y.iter().zip(&seasonal).map(|(v, s)| v - s).collect();
+                let window = self.trend_window();
+                trend = loess(&deseasonalised, window, TREND_DEGREE, Some(&weights), jump(window));
+            }
+
+            if iteration < outer {
+                let residual: Vec<f64> = (0..n).map(|i| y[i] - trend[i] - seasonal[i]).collect();
+                weights = robustness_weights(&residual);
+            }
+        }
+
+        let residual = (0..n).map(|i| y[i] - trend[i] - seasonal[i]).collect();
+        Ok(DecompositionResult {
+            trend,
+            seasonal_components: vec![(self.period, seasonal.clone())],
+            seasonal,
+            residual,
+        })
+    }
+
+    /// Cycle-subseries smoothing followed by removal of its low-pass part
+    fn seasonal_pass(&self, y: &[f64], trend: &[f64], weights: &[f64]) -> Vec<f64> {
+        let n = y.len();
+        let np = self.period;
+        let detrended: Vec<f64> = y.iter().zip(trend).map(|(v, t)| v - t).collect();
+
+        // Smoothed cycle-subseries, extended by one period at each end
+        let mut cycle = vec![0.0; n + 2 * np];
+        for k in 0..np {
+            let sub: Vec<f64> = detrended[k..].iter().step_by(np).copied().collect();
+            let sub_weights: Vec<f64> = weights[k..].iter().step_by(np).copied().collect();
+            let m = sub.len();
+
+            let smoothed = loess(&sub, self.seasonal_window, SEASONAL_DEGREE, Some(&sub_weights), jump(self.seasonal_window));
+            let before = loess_point(&sub, -1, self.seasonal_window, SEASONAL_DEGREE, Some(&sub_weights)).unwrap_or(smoothed[0]);
+            let after = loess_point(&sub, m as isize, self.seasonal_window, SEASONAL_DEGREE, Some(&sub_weights)).unwrap_or(smoothed[m - 1]);
+
+            cycle[k] = before;
+            for (j, value) in smoothed.into_iter().enumerate() {
+                cycle[k + (j + 1) * np] = value;
+            }
+            cycle[k + (m + 1) * np] = after;
+        }
+
+        // Low-pass filter: moving averages of length np, np and 3, then loess
+        let filtered = moving_average(&moving_average(&moving_average(&cycle, np), np), 3);
+        let window = next_odd(np);
+        let low_pass = loess(&filtered, window, LOW_PASS_DEGREE, None, jump(window));
+
+        (0..n).map(|i| cycle[i + np] - low_pass[i]).collect()
+    }
+}
+
+fn check_window(window: usize, what: &str) -> Result<usize> {
+    if window < 3 || window.is_multiple_of(2) {
+        return Err(TelemetryError::InvalidParameter(
+            format!("{} window must be odd and at least 3", what),
+        ));
+    }
+    Ok(window)
+}
+
+fn next_odd(x: usize) -> usize {
+    if x.is_multiple_of(2) { x + 1 } else { x }
+}
+
+/// Evaluation step for loess; points in between are interpolated
+fn jump(window: usize) -> usize {
+    window.div_ceil(10)
+}
+
+fn moving_average(x: &[f64], window: usize) -> Vec<f64> {
+    let mut sum: f64 = x[..window].iter().sum();
+    let mut result = Vec::with_capacity(x.len() - window + 1);
+    result.push(sum / window as f64);
+    for i in window..x.len() {
+        sum += x[i] - x[i - window];
+        result.push(sum / window as f64);
+    }
+    result
+}
+
+/// Loess smooth of `y` (indexed 0..n) evaluated every `jump` points
+fn loess(y: &[f64], window: usize, degree: usize, weights: Option<&[f64]>, jump: usize) -> Vec<f64> {
+    let n = y.len();
+    let mut points: Vec<usize> = (0..n).step_by(jump).collect();
+    if points.last() != Some(&(n - 1)) {
+        points.push(n - 1);
+    }
+
+    let mut result = vec![0.0; n];
+    for &i in &points {
+        result[i] = loess_point(y, i as isize, window, degree, weights).unwrap_or(y[i]);
+    }
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let slope = (result[b] - result[a]) / (b - a) as f64;
+        for i in a + 1..b {
+            result[i] = result[a] + slope * (i - a) as f64;
+        }
+    }
+    result
+}
+
+/// Local polynomial fit at position `x` (which may lie just outside the
+/// data) using the `window` nearest points and tricube weights
+fn loess_point(y: &[f64], x: isize, window: usize, degree: usize, weights: Option<&[f64]>) -> Option<f64> {
+    let n = y.len();
+    let (lo, hi) = if window >= n {
+        (0, n - 1)
+    } else {
+        let lo = (x - (window / 2) as isize).clamp(0, (n - window) as isize) as usize;
+        (lo, lo + window - 1)
+    };
+
+    let xf = x as f64;
+    let mut h = (xf - lo as f64).max(hi as f64 - xf);
+    if window > n {
+        h += ((window - n) / 2) as f64;
+    }
+
+    let mut w: Vec<f64> = (lo..=hi)
+        .map(|j| {
+            let r = (j as f64 - xf).abs();
+            let tricube = if r <= 0.001 * h {
+                1.0
+            } else if r <= 0.999 * h {
+                (1.0 - (r / h).powi(3)).powi(3)
+            } else {
+                0.0
+            };
+            tricube * weights.map_or(1.0, |rw| rw[j])
+        })
+        .collect();
+
+    let total: f64 = w.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    w.iter_mut().for_each(|v| *v /= total);
+
+    if degree > 0 && h > 0.0 {
+        let centre: f64 = w.iter().zip(lo..=hi).map(|(wj, j)| wj * j as f64).sum();
+        let spread: f64 = w.iter().zip(lo..=hi).map(|(wj, j)| wj * (j as f64 - centre).powi(2)).sum();
+        if spread.sqrt() > 0.001 * (n - 1) as f64 {
+            let b = (xf - centre) / spread;
+            for (wj, j) in w.iter_mut().zip(lo..=hi) {
+                *wj *= b * (j as f64 - centre) + 1.0;
+            }
+        }
+    }
+
+    Some(w.iter().zip(&y[lo..=hi]).map(|(wj, yj)| wj * yj).sum())
+}
+
+/// Bisquare weights from the remainder, scaled by six median absolute values
+fn robustness_weights(residual: &[f64]) -> Vec<f64> {
+    let mut abs: Vec<f64> = residual.iter().map(|r| r.abs()).collect();
+    abs.sort_by(|a, b| a.total_cmp(b));
+    let h = 6.0 * quantile_sorted(&abs, 0.5);
+
+    residual
+        .iter()
+        .map(|r| {
+            let u = r.abs();
+            if u <= 0.001 * h {
+                1.0
+            } else if u <= 0.999 * h {
+                (1.0 - (u / h).powi(2)).powi(2)
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Linear trend plus a seasonal pattern whose amplitude doubles
+    fn evolving(n: usize, period: usize) -> (Vec<f64>, Vec<f64>) {
+        let trend: Vec<f64> = (0..n).map(|i| 10.0 + 0.05 * i as f64).collect();
+        let values = (0..n)
+            .map(|i| {
+                let amplitude = 1.0 + i as f64 / n as f64;
+                let phase = 2.0 * std::f64::consts::PI * (i % period) as f64 / period as f64;
+                trend[i] + amplitude * phase.sin()
+            })
+            .collect();
+        (values, trend)
+    }
+
+    #[test]
+    fn test_stl_follows_evolving_seasonality() {
+        let (values, trend) = evolving(240, 12);
+        let ts = TimeSeries::new(values.clone());
+        let result = STL::new(12).unwrap().decompose(&ts).unwrap();
+
+        assert_eq!(result.trend.len(), ts.len());
+        for (i, value) in values.iter().enumerate() {
+            assert!(result.trend[i].is_finite());
+            let total = result.trend[i] + result.seasonal[i] + result.residual[i];
+            assert!((total - value).abs() < 1e-9);
+        }
+        let worst = (12..228).map(|i| (result.trend[i] - trend[i]).abs()).fold(0.0, f64::max);
+        assert!(worst < 0.15);
+
+        // Seasonal peaks grow with the amplitude, from about 1 to about 2
+        assert!((result.seasonal[3] - 1.0).abs() < 0.25);
+        assert!((result.seasonal[231] - 2.0).abs() < 0.25);
+    }
+
+    #[test]
+    fn test_robust_stl_ignores_outliers() {
+        let (mut values, trend) = evolving(240, 12);
+        for i in [50, 51, 130, 200] {
+            values[i] += 40.0;
+        }
+        let ts = TimeSeries::new(values);
+
+        let plain = STL::new(12).unwrap().decompose(&ts).unwrap();
+        let robust = STL::new(12).unwrap().robust().decompose(&ts).unwrap();
+
+        let error = |r: &DecompositionResult| (45..57).map(|i| (r.trend[i] - trend[i]).abs()).fold(0.0, f64::max);
+        assert!(error(&robust) < 0.5);
+        assert!(error(&plain) > 1.0);
+        assert!(robust.residual[130] > 35.0);
+    }
+
+    #[test]
+    fn test_stl_parameters() {
+        assert!(STL::new(1).is_err());
+        assert!(STL::new(12).unwrap().with_seasonal_window(8).is_err());
+        assert!(STL::new(12).unwrap().with_trend_window(1).is_err());
+        assert!(STL::new(12).unwrap().with_iterations(0, 5).is_err());
+
+        let stl = STL::new(12).unwrap().with_seasonal_window(13).unwrap();
+        assert_eq!(stl.trend_window(), 21);
+        assert!(stl.decompose(&TimeSeries::new(vec![1.0; 20])).is_err());
+
+        let mut values = vec![1.0; 48];
+        values[3] = f64::NAN;
+        assert!(stl.decompose(&TimeSeries::new(values)).is_err());
+    }
+}
@@ -0,0 +1,596 @@
+//! ETS (Error, Trend, Seasonal) exponential smoothing state-space models
+//!
+//! Covers the 30 combinations of additive/multiplicative errors, none /
+//! additive / multiplicative (optionally damped) trends and none / additive /
+//! multiplicative seasonality. Smoothing parameters and initial level/trend
+//! are estimated by maximum likelihood; initial seasonal indices come from
+//! the first two seasons. Prediction intervals are analytical for the linear
+//! additive-error models and simulated otherwise.
+
+use crate::{Result, TelemetryError, TimeSeries};
+use crate::forecasting::{simulate_intervals, z_score, ForecastResult, Forecaster};
+use crate::models::arima::{aic, aicc, bic};
+use crate::optim::{from_interval, nelder_mead, to_interval};
+
+/// Bounds of the damping parameter
+const PHI_BOUNDS: (f64, f64) = (0.8, 0.98);
+
+/// Error component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorType {
+    Additive,
+    Multiplicative,
+}
+
+/// Trend component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendType {
+    None,
+    Additive,
+    AdditiveDamped,
+    Multiplicative,
+    MultiplicativeDamped,
+}
+
+/// Seasonal component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeasonalType {
+    None,
+    Additive,
+    Multiplicative,
+}
+
+impl TrendType {
+    fn is_damped(&self) -> bool {
+        matches!(self, TrendType::AdditiveDamped | TrendType::MultiplicativeDamped)
+    }
+
+    fn is_multiplicative(&self) -> bool {
+        matches!(self, TrendType::Multiplicative | TrendType::MultiplicativeDamped)
+    }
+}
+
+/// Smoothing parameters in the smoothing-equation form
+#[derive(Debug, Clone, Copy)]
+pub struct ETSParams {
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub phi: f64,
+}
+
+/// Model state: level, trend and the last `period` seasonal indices
+#[derive(Debug, Clone)]
+struct State {
+    level: f64,
+    trend: f64,
+    /// `seasonal[0]` is the index for the next time step
+    seasonal: Vec<f64>,
+}
+
+/// Result of running the model recursions over a series
+struct Filtered {
+    state: State,
+    residuals: Vec<f64>,
+    sum_sq: f64,
+    sum_log_mu: f64,
+}
+
+#[derive(Debug, Clone)]
+struct ETSFit {
+    params: ETSParams,
+    state: State,
+    residuals: Vec<f64>,
+    sigma2: f64,
+    log_likelihood: f64,
+    n: usize,
+}
+
+/// ETS state-space model
+#[derive(Debug, Clone)]
+pub struct ETS {
+    error: ErrorType,
+    trend: TrendType,
+    seasonal: SeasonalType,
+    period: usize,
+    fit: Option<ETSFit>,
+}
+
+impl ETS {
+    /// Create an ETS model; `period` is ignored for non-seasonal models
+    pub fn new(error: ErrorType, trend: TrendType, seasonal: SeasonalType, period: usize) -> Result<Self> {
+        if seasonal != SeasonalType::None && period < 2 {
+            return Err(TelemetryError::InvalidParameter(
+                "Seasonal period must be at least 2".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            error,
+            trend,
+            seasonal,
+            period: if seasonal == SeasonalType::None { 1 } else { period },
+            fit: None,
+        })
+    }
+
+    /// Model name in ETS(E,T,S) notation, e.g. `ETS(M,Ad,A)`
+    pub fn name(&self) -> String {
+        let error = match self.error {
+            ErrorType::Additive => "A",
+            ErrorType::Multiplicative => "M",
+        };
+        let trend = match self.trend {
+            TrendType::None => "N",
+            TrendType::Additive => "A",
+            TrendType::AdditiveDamped => "Ad",
+            TrendType::Multiplicative => "M",
+            TrendType::MultiplicativeDamped => "Md",
+        };
+        let seasonal = match self.seasonal {
+            SeasonalType::None => "N",
+            SeasonalType::Additive => "A",
+            SeasonalType::Multiplicative => "M",
+        };
+        format!("ETS({},{},{})", error, trend, seasonal)
+    }
+
+    /// Fitted smoothing parameters
+    pub fn params(&self) -> Option<ETSParams> {
+        self.fit.as_ref().map(|f| f.params)
+    }
+
+    /// Innovation variance (relative for multiplicative errors)
+    pub fn sigma2(&self) -> Option<f64> {
+        self.fit.as_ref().map(|f| f.sigma2)
+    }
+
+    /// One-step innovations (relative errors for multiplicative errors)
+    pub fn residuals(&self) -> Option<&[f64]> {
+        self.fit.as_ref().map(|f| f.residuals.as_slice())
+    }
+
+    /// Maximised log-likelihood
+    pub fn log_likelihood(&self) -> Option<f64> {
+        self.fit.as_ref().map(|f| f.log_likelihood)
+    }
+
+    /// Akaike information criterion
+    pub fn aic(&self) -> Option<f64> {
+        self.log_likelihood().map(|ll| aic(ll, self.n_params()))
+    }
+
+    /// Small-sample corrected AIC
+    pub fn aicc(&self) -> Option<f64> {
+        self.fit.as_ref().map(|f| aicc(f.log_likelihood, self.n_params(), f.n))
+    }
+
+    /// Bayesian information criterion
+    pub fn bic(&self) -> Option<f64> {
+        self.fit.as_ref().map(|f| bic(f.log_likelihood, self.n_params(), f.n))
+    }
+
+    fn has_trend(&self) -> bool {
+        self.trend != TrendType::None
+    }
+
+    fn has_season(&self) -> bool {
+        self.seasonal != SeasonalType::None
+    }
+
+    fn requires_positive(&self) -> bool {
+        self.error == ErrorType::Multiplicative
+            || self.trend.is_multiplicative()
+            || self.seasonal == SeasonalType::Multiplicative
+    }
+
+    /// Linear models with additive errors have closed-form forecast variances
+    fn has_analytical_variance(&self) -> bool {
+        self.error == ErrorType::Additive
+            && !self.trend.is_multiplicative()
+            && self.seasonal != SeasonalType::Multiplicative
+    }
+
+    /// Parameters counted by the information criteria: smoothing parameters,
+    /// initial states and the innovation variance
+    fn n_params(&self) -> usize {
+        let smoothing = 1
+            + usize::from(self.has_trend())
+            + usize::from(self.has_season())
+            + usize::from(self.trend.is_damped());
+        let initial = 1 + usize::from(self.has_trend()) + if self.has_season() { self.period - 1 } else { 0 };
+        smoothing + initial + 1
+    }
+
+    /// Level plus trend after `h` steps, ignoring seasonality
+    fn trended(&self, state: &State, phi: f64, h: usize) -> f64 {
+        let phi_h: f64 = if self.trend.is_damped() {
+            (1..=h).map(|j| phi.powi(j as i32)).sum()
+        } else {
+            h as f64
+        };
+        match self.trend {
+            TrendType::None => state.level,
+            TrendType::Additive | TrendType::AdditiveDamped => state.level + phi_h * state.trend,
+            TrendType::Multiplicative | TrendType::MultiplicativeDamped => {
+                state.level * state.trend.powf(phi_h)
+            }
+        }
+    }
+
+    /// Point forecast `h` steps ahead of `state`
+    fn point(&self, state: &State, phi: f64, h: usize) -> f64 {
+        let base = self.trended(state, phi, h);
+        match self.seasonal {
+            SeasonalType::None => base,
+            SeasonalType::Additive => base + state.seasonal[(h - 1) % self.period],
+            SeasonalType::Multiplicative => base * state.seasonal[(h - 1) % self.period],
+        }
+    }
+
+    /// Update `state` with observation `y`, returning the one-step forecast
+    fn step(&self, state: &mut State, p: &ETSParams, y: f64) -> f64 {
+        let mu = self.point(state, p.phi, 1);
+        let base = self.trended(state, p.phi, 1);
+        let s_old = if self.has_season() { state.seasonal[0] } else { 0.0 };
+
+        let deseasonalised = match self.seasonal {
+            SeasonalType::None => y,
+            SeasonalType::Additive => y - s_old,
+            SeasonalType::Multiplicative => y / s_old,
+        };
+        let level = p.alpha * deseasonalised + (1.0 - p.alpha) * base;
+
+        let damp = if self.trend.is_damped() { p.phi } else { 1.0 };
+        state.trend = match self.trend {
+            TrendType::None => 0.0,
+            TrendType::Additive | TrendType::AdditiveDamped => {
+                p.beta * (level - state.level) + (1.0 - p.beta) * damp * state.trend
+            }
+            TrendType::Multiplicative | TrendType::MultiplicativeDamped => {
+                p.beta * (level / state.level) + (1.0 - p.beta) * state.trend.powf(damp)
+            }
+        };
+
+        if self.has_season() {
+            let s_new = match self.seasonal {
+                SeasonalType::Additive => p.gamma * (y - base) + (1.0 - p.gamma) * s_old,
+                _ => p.gamma * (y / base) + (1.0 - p.gamma) * s_old,
+            };
+            state.seasonal.remove(0);
+            state.seasonal.push(s_new);
+        }
+
+        state.level = level;
+        mu
+    }
+
+    /// Run the recursions over `values` from the initial `state`
+    fn filter(&self, values: &[f64], p: &ETSParams, mut state: State) -> Option<Filtered> {
+        let mut residuals = Vec::with_capacity(values.len());
+        let mut sum_sq = 0.0;
+        let mut sum_log_mu = 0.0;
+
+        for &y in values {
+            let mu = self.step(&mut state, p, y);
+            if !mu.is_finite() {
+                return None;
+            }
+
+            let e = match self.error {
+                ErrorType::Additive => y - mu,
+                ErrorType::Multiplicative => {
+                    if mu <= 0.0 {
+                        return None;
+                    }
+                    sum_log_mu += mu.ln();
+                    (y - mu) / mu
+                }
+            };
+            sum_sq += e * e;
+            residuals.push(e);
+        }
+
+        Some(Filtered {
+            state,
+            residuals,
+            sum_sq,
+            sum_log_mu,
+        })
+    }
+
+    fn log_likelihood_of(&self, filtered: &Filtered, n: usize) -> f64 {
+        let sigma2 = (filtered.sum_sq / n as f64).max(f64::MIN_POSITIVE);
+        -0.5 * n as f64 * ((2.0 * std::f64::consts::PI * sigma2).ln() + 1.0) - filtered.sum_log_mu
+    }
+
+    /// Heuristic initial states from the start of the series
+    fn initial_state(&self, values: &[f64]) -> State {
+        let m = self.period;
+        let (level, slope, seasonal) = if self.has_season() {
+            let first = values[..m].iter().sum::<f64>() / m as f64;
+            let second = values[m..2 * m].iter().sum::<f64>() / m as f64;
+            let seasonal = values[..m]
+                .iter()
+                .map(|&y| match self.seasonal {
+                    SeasonalType::Multiplicative => y / first,
+                    _ => y - first,
+                })
+                .collect();
+            (first, (second - first) / m as f64, seasonal)
+        } else {
+            (values[0], values[1] - values[0], Vec::new())
+        };
+
+        let trend = match self.trend {
+            TrendType::None => 0.0,
+            TrendType::Additive | TrendType::AdditiveDamped => slope,
+            TrendType::Multiplicative | TrendType::MultiplicativeDamped => {
+                ((level + slope) / level).clamp(0.5, 2.0)
+            }
+        };
+
+        State { level, trend, seasonal }
+    }
+
+    /// Map optimiser coordinates to smoothing parameters and initial state
+    fn unpack(&self, x: &[f64], init: &State, scale: f64) -> (ETSParams, State) {
+        let mut i = 0;
+        let mut next = || {
+            i += 1;
+            x[i - 1]
+        };
+
+        let alpha = to_interval(next(), 0.0, 1.0);
+        let beta = if self.has_trend() { to_interval(next(), 0.0, 1.0) } else { 0.0 };
+        let gamma = if self.has_season() { to_interval(next(), 0.0, 1.0) } else { 0.0 };
+        let phi = if self.trend.is_damped() {
+            to_interval(next(), PHI_BOUNDS.0, PHI_BOUNDS.1)
+        } else {
+            1.0
+        };
+
+        let mut state = init.clone();
+        state.level += next() * scale;
+        if self.trend.is_multiplicative() {
+            state.trend *= (0.1 * next()).exp();
+        } else if self.has_trend() {
+            state.trend += next() * scale * 0.1;
+        }
+
+        (ETSParams { alpha, beta, gamma, phi }, state)
+    }
+}
+
+impl Forecaster for ETS {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        let min_len = if self.has_season() { 2 * self.period } else { 3 };
+        if ts.len() < min_len.max(self.n_params() + 1) {
+            return Err(TelemetryError::InsufficientData(
+                format!("Need at least {} data points for {}", min_len.max(self.n_params() + 1), self.name()),
+            ));
+        }
+        if ts.has_missing() {
+            return Err(TelemetryError::InvalidData(
+                "ETS cannot be fitted on a series with missing values".to_string(),
+            ));
+        }
+        if self.requires_positive() && ts.values.iter().any(|&v| v <= 0.0) {
+            return Err(TelemetryError::InvalidData(
+                format!("{} requires strictly positive data", self.name()),
+            ));
+        }
+
+        let values = &ts.values;
+        let n = values.len();
+        let init = self.initial_state(values);
+        let stats = ts.statistics();
+        let scale = stats.std_dev.max(stats.mean.abs() * 1e-3).max(1e-8);
+
+        let mut x0 = vec![from_interval(0.3, 0.0, 1.0)];
+        if self.has_trend() {
+            x0.push(from_interval(0.1, 0.0, 1.0));
+        }
+        if self.has_season() {
+            x0.push(from_interval(0.1, 0.0, 1.0));
+        }
+        if self.trend.is_damped() {
+            x0.push(from_interval(0.95, PHI_BOUNDS.0, PHI_BOUNDS.1));
+        }
+        x0.push(0.0);
+        if self.has_trend() {
+            x0.push(0.0);
+        }
+
+        let objective = |x: &[f64]| {
+            let (p, state) = self.unpack(x, &init, scale);
+            match self.filter(values, &p, state) {
+                Some(f) => -self.log_likelihood_of(&f, n),
+                None => f64::INFINITY,
+            }
+        };
+
+        let (best, _) = nelder_mead(objective, &x0, 0.5, 3000, 1e-10);
+        let (best, value) = nelder_mead(objective, &best, 0.1, 3000, 1e-12);
+        if !value.is_finite() {
+            return Err(TelemetryError::ModelError(
+                format!("{} could not be fitted to this series", self.name()),
+            ));
+        }
+
+        let (params, state) = self.unpack(&best, &init, scale);
+        let filtered = self.filter(values, &params, state).ok_or_else(|| {
+            TelemetryError::ModelError(format!("{} could not be fitted to this series", self.name()))
+        })?;
+
+        self.fit = Some(ETSFit {
+            params,
+            log_likelihood: self.log_likelihood_of(&filtered, n),
+            sigma2: filtered.sum_sq / n as f64,
+            state: filtered.state,
+            residuals: filtered.residuals,
+            n,
+        });
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let fit = self.fit.as_ref()
+            .ok_or_else(|| TelemetryError::ModelError(
+                "Model must be fitted before forecasting".to_string()
+            ))?;
+
+        let predictions = (1..=steps)
+            .map(|h| self.point(&fit.state, fit.params.phi, h))
+            .collect();
+
+        Ok(ForecastResult {
+            predictions,
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.7,
+            intervals: Vec::new(),
+        })
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        let fit = self.fit.as_ref().unwrap();
+
+        if self.has_analytical_variance() {
+            let p = &fit.params;
+            let mut factor = 1.0;
+            let mut std_errors = Vec::with_capacity(steps);
+            for j in 1..=steps {
+                std_errors.push((fit.sigma2 * factor).sqrt());
+
+                // Coefficient of the j-step-old innovation in the forecast error
+                let phi_j: f64 = if self.trend.is_damped() {
+                    (1..=j).map(|i| p.phi.powi(i as i32)).sum()
+                } else {
+                    j as f64
+                };
+                let mut c = p.alpha + if self.has_trend() { p.alpha * p.beta * phi_j } else { 0.0 };
+                if self.has_season() && j % self.period == 0 {
+                    c += p.gamma;
+                }
+                factor += c * c;
+            }
+            ForecastResult::with_normal_interval(base_forecast, &std_errors, confidence_level)
+        } else {
+            z_score(confidence_level)?;
+            let (lower, upper) = simulate_intervals(&fit.state, fit.sigma2, steps, confidence_level, |state, _, e| {
+                let mu = self.point(state, fit.params.phi, 1);
+                let y = match self.error {
+                    ErrorType::Additive => mu + e,
+                    ErrorType::Multiplicative => mu * (1.0 + e),
+                };
+                self.step(state, &fit.params, y);
+                y
+            });
+            Ok(ForecastResult::with_bounds(base_forecast, lower, upper, confidence_level))
+        }
+    }
+}
+
+/// Fit every admissible ETS model and return the one with the lowest AICc.
+///
+/// Seasonal models are considered when `period` is given and the series
+/// covers at least two seasons; multiplicative components only for strictly
+/// positive data. As is customary, multiplicative trends and the numerically
+/// unstable additive-error models with multiplicative trend or seasonality
+/// are excluded.
+pub fn auto_ets(ts: &TimeSeries, period: Option<usize>) -> Result<ETS> {
+    let positive = ts.values.iter().all(|&v| v > 0.0);
+    let seasonal_period = period.filter(|&m| m >= 2 && ts.len() >= 2 * m);
+
+    let errors = [ErrorType::Additive, ErrorType::Multiplicative];
+    let trends = [TrendType::None, TrendType::Additive, TrendType::AdditiveDamped];
+    let seasonals: Vec<SeasonalType> = match seasonal_period {
+        Some(_) => vec![SeasonalType::None, SeasonalType::Additive, SeasonalType::Multiplicative],
+        None => vec![SeasonalType::None],
+    };
+
+    let mut best: Option<(f64, ETS)> = None;
+    for &error in &errors {
+        for &trend in &trends {
+            for &seasonal in &seasonals {
+                if error == ErrorType::Additive && seasonal == SeasonalType::Multiplicative {
+                    continue;
+                }
+
+                let mut model = ETS::new(error, trend, seasonal, seasonal_period.unwrap_or(1))?;
+                if model.requires_positive() && !positive {
+                    continue;
+                }
+                if model.fit(ts).is_err() {
+                    continue;
+                }
+
+                let score = model.aicc().unwrap_or(f64::INFINITY);
+                if score.is_finite() && best.as_ref().is_none_or(|(b, _)| score < *b) {
+                    best = Some((score, model));
+                }
+            }
+        }
+    }
+
+    best.map(|(_, model)| model).ok_or_else(|| {
+        TelemetryError::ModelError("No ETS model could be fitted to this series".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::seeded_shocks;
+
+    #[test]
+    fn test_ann_matches_level_series() {
+        let e = seeded_shocks(200, 1);
+        let data: Vec<f64> = e.iter().map(|x| 20.0 + x).collect();
+
+        let mut model = ETS::new(ErrorType::Additive, TrendType::None, SeasonalType::None, 1).unwrap();
+        model.fit(&TimeSeries::new(data)).unwrap();
+
+        assert_eq!(model.name(), "ETS(A,N,N)");
+        assert!(model.params().unwrap().alpha < 0.3);
+        assert!((model.sigma2().unwrap() - 1.0).abs() < 0.25);
+
+        let ci = model.forecast_with_confidence(5, 0.8).unwrap();
+        let width = ci.upper_bound.unwrap()[0] - ci.lower_bound.unwrap()[0];
+        // 80% interval of a unit-variance innovation is about 2 * 1.2816 wide
+        assert!((width - 2.0 * 1.2816).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_multiplicative_seasonal_simulated_intervals() {
+        let pattern = [1.3, 0.9, 0.7, 1.1];
+        let e = seeded_shocks(64, 2);
+        let data: Vec<f64> = (0..64)
+            .map(|i| (100.0 + 2.0 * i as f64) * pattern[i % 4] * (1.0 + 0.02 * e[i]))
+            .collect();
+
+        let mut model = ETS::new(ErrorType::Multiplicative, TrendType::Additive, SeasonalType::Multiplicative, 4)
+            .unwrap();
+        model.fit(&TimeSeries::new(data)).unwrap();
+
+        let ci = model.forecast_with_confidence(8, 0.95).unwrap();
+        for h in 0..8 {
+            let expected = (100.0 + 2.0 * (64 + h) as f64) * pattern[h % 4];
+            assert!((ci.predictions[h] - expected).abs() / expected < 0.05);
+            assert!(ci.lower_bound.as_ref().unwrap()[h] < ci.predictions[h]);
+            assert!(ci.upper_bound.as_ref().unwrap()[h] > ci.predictions[h]);
+        }
+    }
+
+    #[test]
+    fn test_auto_ets_picks_seasonal_model() {
+        let pattern = [10.0, -5.0, 2.0, -7.0];
+        let e = seeded_shocks(80, 3);
+        let data: Vec<f64> = (0..80).map(|i| 50.0 + pattern[i % 4] + 0.5 * e[i]).collect();
+
+        let model = auto_ets(&TimeSeries::new(data), Some(4)).unwrap();
+        assert!(model.name().ends_with(",A)") || model.name().ends_with(",M)"));
+    }
+}
@@ -1,5 +1,6 @@
 //! Feature engineering for time series
 
+use crate::rolling::Sweep;
 use crate::{Result, TelemetryError, TimeSeries};
 
 /// Feature extractor for time series data
@@ -34,7 +35,9 @@ impl FeatureExtractor {
         Ok(features)
     }
 
-    /// Calculate rolling statistics (mean, std, min, max)
+    /// Calculate rolling statistics (mean, std, min, max) in O(n)
+    ///
+    /// Windows containing a missing (`NaN`) value yield `NaN` for every statistic.
     pub fn rolling_statistics(ts: &TimeSeries, window: usize) -> Result<RollingStats> {
         if window == 0 {
             return Err(TelemetryError::InvalidParameter(
@@ -53,24 +56,21 @@ impl FeatureExtractor {
         let mut mins = Vec::new();
         let mut maxs = Vec::new();
 
+        let mut sweep = Sweep::new(&ts.values, false);
         for i in 0..=(ts.len() - window) {
-            let window_data = &ts.values[i..i + window];
-
-            let mean = window_data.iter().sum::<f64>() / window as f64;
-            means.push(mean);
-
-            let variance = window_data.iter()
-                .map(|x| (x - mean).powi(2))
-                .sum::<f64>() / window as f64;
-            stds.push(variance.sqrt());
-
-            mins.push(*window_data.iter()
-                .min_by(|a, b| a.partial_cmp(b).unwrap())
-                .unwrap());
+            let acc = sweep.advance(i, i + window);
+            if acc.missing() > 0 {
+                means.push(f64::NAN);
+                stds.push(f64::NAN);
+                mins.push(f64::NAN);
+                maxs.push(f64::NAN);
+                continue;
+            }
 
-            maxs.push(*window_data.iter()
-                .max_by(|a, b| a.partial_cmp(b).unwrap())
-                .unwrap());
+            means.push(acc.mean());
+            stds.push(acc.std());
+            mins.push(acc.min());
+            maxs.push(acc.max());
         }
 
         Ok(RollingStats {
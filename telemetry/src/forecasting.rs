@@ -1,6 +1,21 @@
 //! Forecasting models and algorithms
 
 use crate::{Result, TelemetryError, TimeSeries};
+use crate::optim::{from_interval, nelder_mead, to_interval};
+use crate::time_series::quantile_sorted;
+use ndarray::Array2;
+use statrs::distribution::{ContinuousCDF, Normal, StudentsT};
+
+/// Prediction interval at one confidence level
+#[derive(Debug, Clone)]
+pub struct PredictionInterval {
+    /// Nominal coverage, e.g. 0.95
+    pub level: f64,
+    /// Lower bound for each horizon
+    pub lower: Vec<f64>,
+    /// Upper bound for each horizon
+    pub upper: Vec<f64>,
+}
 
 /// Result of a forecast operation
 #[derive(Debug, Clone)]
@@ -11,8 +26,39 @@ pub struct ForecastResult {
     pub lower_bound: Option<Vec<f64>>,
     /// Upper confidence bound (if available)
     pub upper_bound: Option<Vec<f64>>,
-    /// Model confidence/accuracy metrics
+    /// Confidence level of `lower_bound` / `upper_bound`; for point
+    /// forecasts a fixed model-specific score, not a measure of accuracy
+    /// (see [`crate::metrics`])
     pub confidence: f64,
+    /// Prediction intervals for every requested level; the first one is
+    /// also exposed through `lower_bound` / `upper_bound`
+    pub intervals: Vec<PredictionInterval>,
+}
+
+impl ForecastResult {
+    /// Attach a prediction interval to a point forecast
+    pub(crate) fn with_bounds(base: ForecastResult, lower: Vec<f64>, upper: Vec<f64>, level: f64) -> Self {
+        ForecastResult {
+            predictions: base.predictions,
+            lower_bound: Some(lower.clone()),
+            upper_bound: Some(upper.clone()),
+            confidence: level,
+            intervals: vec![PredictionInterval { level, lower, upper }],
+        }
+    }
+
+    /// Attach a Gaussian prediction interval given per-horizon standard errors
+    pub(crate) fn with_normal_interval(base: ForecastResult, std_errors: &[f64], level: f64) -> Result<Self> {
+        let z = z_score(level)?;
+        let lower = base.predictions.iter().zip(std_errors).map(|(p, se)| p - z * se).collect();
+        let upper = base.predictions.iter().zip(std_errors).map(|(p, se)| p + z * se).collect();
+        Ok(Self::with_bounds(base, lower, upper, level))
+    }
+
+    /// Prediction interval at the given level, if it was computed
+    pub fn interval(&self, level: f64) -> Option<&PredictionInterval> {
+        self.intervals.iter().find(|i| (i.level - level).abs() < 1e-9)
+    }
 }
 
 /// Forecaster trait for different forecasting models
@@ -25,12 +71,111 @@ pub trait Forecaster {
 
     /// Forecast with confidence intervals
     fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult>;
+
+    /// Forecast with prediction intervals at several levels (e.g. 0.5, 0.8, 0.95)
+    fn forecast_with_levels(&self, steps: usize, levels: &[f64]) -> Result<ForecastResult> {
+        let (&first, rest) = levels.split_first().ok_or_else(|| {
+            TelemetryError::InvalidParameter("At least one confidence level is required".to_string())
+        })?;
+
+        let mut result = self.forecast_with_confidence(steps, first)?;
+        for &level in rest {
+            let other = self.forecast_with_confidence(steps, level)?;
+            result.intervals.extend(other.intervals);
+        }
+        Ok(result)
+    }
+}
+
+/// Forecaster driven by exogenous regressors
+///
+/// Regressor matrices have one row per time step and one column per
+/// variable; the rows passed to `fit_with_regressors` are aligned with the
+/// series and those passed when forecasting cover the forecast horizon.
+pub trait ExogenousForecaster {
+    /// Fit the model to a series and its aligned regressors
+    fn fit_with_regressors(&mut self, ts: &TimeSeries, regressors: &Array2<f64>) -> Result<()>;
+
+    /// Forecast one step per row of `future` regressor values
+    fn forecast_with_regressors(&self, future: &Array2<f64>) -> Result<ForecastResult>;
+
+    /// Forecast with prediction intervals, given future regressor values
+    fn forecast_with_regressors_confidence(&self, future: &Array2<f64>, confidence_level: f64) -> Result<ForecastResult>;
+}
+
+/// Two-sided standard normal quantile for a central interval of `confidence_level`
+pub(crate) fn z_score(confidence_level: f64) -> Result<f64> {
+    if confidence_level <= 0.0 || confidence_level >= 1.0 {
+        return Err(TelemetryError::InvalidParameter(
+            "Confidence level must be between 0 and 1".to_string(),
+        ));
+    }
+
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    Ok(normal.inverse_cdf(0.5 + confidence_level / 2.0))
+}
+
+/// Number of sample paths used for simulated prediction intervals
+const SIMULATION_PATHS: usize = 5000;
+
+/// Interval quantiles of simulated future sample paths
+///
+/// Each path starts from a clone of `state`; `step(state, h, e)` draws the
+/// observation `h` steps after the first with N(0, `sigma2`) error `e`,
+/// updates the state with it and returns it.
+pub(crate) fn simulate_intervals<S: Clone>(
+    state: &S,
+    sigma2: f64,
+    steps: usize,
+    confidence_level: f64,
+    mut step: impl FnMut(&mut S, usize, f64) -> f64,
+) -> (Vec<f64>, Vec<f64>) {
+    let normal = Normal::new(0.0, sigma2.sqrt().max(f64::MIN_POSITIVE)).unwrap();
+    let mut rng = SplitMix64(0x5EED);
+    let mut paths = vec![Vec::with_capacity(SIMULATION_PATHS); steps];
+
+    for _ in 0..SIMULATION_PATHS {
+        let mut path_state = state.clone();
+        for (h, path) in paths.iter_mut().enumerate() {
+            let e = normal.inverse_cdf(rng.next_open01());
+            path.push(step(&mut path_state, h, e));
+        }
+    }
+
+    let tail = (1.0 - confidence_level) / 2.0;
+    paths
+        .iter_mut()
+        .map(|draws| {
+            draws.retain(|v| v.is_finite());
+            draws.sort_by(|a, b| a.total_cmp(b));
+            (quantile_sorted(draws, tail), quantile_sorted(draws, 1.0 - tail))
+        })
+        .unzip()
+}
+
+/// Small deterministic generator so simulated intervals are reproducible
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Uniform draw in the open interval (0, 1)
+    fn next_open01(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        ((z >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
 }
 
 /// Simple exponential smoothing forecaster
+///
+/// Equivalent to an ETS(A,N,N) model with a fixed `alpha`; prediction
+/// intervals use its forecast variance `σ²(1 + (h - 1)α²)`.
 pub struct ExponentialSmoothing {
     alpha: f64,
     last_value: Option<f64>,
+    sigma2: f64,
     fitted: bool,
 }
 
@@ -46,6 +191,7 @@ impl ExponentialSmoothing {
         Ok(Self {
             alpha,
             last_value: None,
+            sigma2: 0.0,
             fitted: false,
         })
     }
@@ -59,13 +205,16 @@ impl Forecaster for ExponentialSmoothing {
             ));
         }
 
-        // Calculate the final smoothed value
+        // Calculate the final smoothed value and the one-step errors
         let mut smoothed = ts.values[0];
+        let mut sse = 0.0;
         for &value in ts.values.iter().skip(1) {
+            sse += (value - smoothed).powi(2);
             smoothed = self.alpha * value + (1.0 - self.alpha) * smoothed;
         }
 
         self.last_value = Some(smoothed);
+        self.sigma2 = if ts.len() > 1 { sse / (ts.len() - 1) as f64 } else { 0.0 };
         self.fitted = true;
         Ok(())
     }
@@ -85,34 +234,27 @@ impl Forecaster for ExponentialSmoothing {
             lower_bound: None,
             upper_bound: None,
             confidence: 0.7,
+            intervals: Vec::new(),
         })
     }
 
     fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
         let base_forecast = self.forecast(steps)?;
-        let last_value = self.last_value.unwrap();
-
-        // Simple confidence interval based on confidence level
-        let margin = last_value * (1.0 - confidence_level) * 0.5;
-
-        let lower_bound = base_forecast.predictions.iter()
-            .map(|&v| v - margin)
-            .collect();
 
-        let upper_bound = base_forecast.predictions.iter()
-            .map(|&v| v + margin)
+        let std_errors: Vec<f64> = (1..=steps)
+            .map(|h| (self.sigma2 * (1.0 + (h - 1) as f64 * self.alpha.powi(2))).sqrt())
             .collect();
 
-        Ok(ForecastResult {
-            predictions: base_forecast.predictions,
-            lower_bound: Some(lower_bound),
-            upper_bound: Some(upper_bound),
-            confidence: confidence_level,
-        })
+        ForecastResult::with_normal_interval(base_forecast, &std_errors, confidence_level)
     }
 }
 
 /// Moving average forecaster
+///
+/// Prediction intervals treat the moving average as simple exponential
+/// smoothing with the same average age, `α = 2 / (window + 1)`, so the
+/// in-sample one-step error variance grows as `σ²(1 + (h - 1)α²)`. This is an
+/// approximation; the moving average has no underlying stochastic model.
 pub struct MovingAverageForecaster {
     window: usize,
     history: Option<Vec<f64>>,
@@ -163,40 +305,812 @@ impl Forecaster for MovingAverageForecaster {
             lower_bound: None,
             upper_bound: None,
             confidence: 0.6,
+            intervals: Vec::new(),
         })
     }
 
     fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
         let base_forecast = self.forecast(steps)?;
+        z_score(confidence_level)?;
         let history = self.history.as_ref().unwrap();
 
-        // Calculate std dev of recent values
-        let last_values = &history[history.len() - self.window..];
-        let mean: f64 = last_values.iter().sum::<f64>() / self.window as f64;
-        let variance: f64 = last_values.iter()
-            .map(|x| (x - mean).powi(2))
-            .sum::<f64>() / self.window as f64;
-        let std_dev = variance.sqrt();
-
-        // Use std dev for confidence intervals
-        let z_score = if confidence_level >= 0.95 { 1.96 } else { 1.645 };
-        let margin = z_score * std_dev;
-
-        let lower_bound = base_forecast.predictions.iter()
-            .map(|&v| v - margin)
+        // Spread of the in-sample one-step errors of the moving average, or of
+        // the last window itself when there is no history beyond it
+        let errors: Vec<f64> = (self.window..history.len())
+            .map(|t| {
+                let window = &history[t - self.window..t];
+                history[t] - window.iter().sum::<f64>() / self.window as f64
+            })
+            .collect();
+        let samples: Vec<f64> = if errors.len() >= 2 {
+            errors
+        } else {
+            let last_values = &history[history.len() - self.window..];
+            let mean = last_values.iter().sum::<f64>() / self.window as f64;
+            last_values.iter().map(|x| x - mean).collect()
+        };
+
+        let n = samples.len() as f64;
+        let variance = samples.iter().map(|e| e * e).sum::<f64>() / (n - 1.0).max(1.0);
+
+        // Student-t quantile accounts for the estimated variance
+        let quantile = match StudentsT::new(0.0, 1.0, (n - 1.0).max(1.0)) {
+            Ok(t) => t.inverse_cdf(0.5 + confidence_level / 2.0),
+            Err(_) => z_score(confidence_level)?,
+        };
+        let alpha = 2.0 / (self.window as f64 + 1.0);
+        let margins: Vec<f64> = (0..steps)
+            .map(|h| quantile * (variance * (1.0 + h as f64 * alpha * alpha)).sqrt())
+            .collect();
+
+        let lower_bound = base_forecast.predictions.iter().zip(&margins)
+            .map(|(&v, m)| v - m)
             .collect();
 
-        let upper_bound = base_forecast.predictions.iter()
-            .map(|&v| v + margin)
+        let upper_bound = base_forecast.predictions.iter().zip(&margins)
+            .map(|(&v, m)| v + m)
             .collect();
 
+        Ok(ForecastResult::with_bounds(base_forecast, lower_bound, upper_bound, confidence_level))
+    }
+}
+
+/// Check that a series is long enough and has no missing values
+fn check_baseline_input(ts: &TimeSeries, min_len: usize, name: &str) -> Result<()> {
+    if ts.len() < min_len {
+        return Err(TelemetryError::InsufficientData(
+            format!("{} needs at least {} data points", name, min_len),
+        ));
+    }
+    if ts.has_missing() {
+        return Err(TelemetryError::InvalidData(
+            format!("{} cannot be fitted on a series with missing values", name),
+        ));
+    }
+    Ok(())
+}
+
+fn not_fitted() -> TelemetryError {
+    TelemetryError::ModelError("Model must be fitted before forecasting".to_string())
+}
+
+/// Mean forecaster: every forecast is the sample mean
+///
+/// Intervals use `σ·sqrt(1 + 1/T)` with σ the sample standard deviation.
+#[derive(Debug, Clone, Default)]
+pub struct Mean {
+    mean: Option<f64>,
+    sigma2: f64,
+    n: usize,
+}
+
+impl Mean {
+    /// Create a new mean forecaster
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Forecaster for Mean {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        check_baseline_input(ts, 2, "Mean forecaster")?;
+
+        let n = ts.len();
+        let mean = ts.values.iter().sum::<f64>() / n as f64;
+        self.sigma2 = ts.values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        self.mean = Some(mean);
+        self.n = n;
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let mean = self.mean.ok_or_else(not_fitted)?;
+
         Ok(ForecastResult {
-            predictions: base_forecast.predictions,
-            lower_bound: Some(lower_bound),
-            upper_bound: Some(upper_bound),
-            confidence: confidence_level,
+            predictions: vec![mean; steps],
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.5,
+            intervals: Vec::new(),
         })
     }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        let se = (self.sigma2 * (1.0 + 1.0 / self.n as f64)).sqrt();
+        ForecastResult::with_normal_interval(base_forecast, &vec![se; steps], confidence_level)
+    }
+}
+
+/// Naive (random walk) forecaster: every forecast is the last observation
+///
+/// Intervals use `σ·sqrt(h)` with σ² the mean squared first difference.
+#[derive(Debug, Clone, Default)]
+pub struct Naive {
+    last_value: Option<f64>,
+    sigma2: f64,
+}
+
+impl Naive {
+    /// Create a new naive forecaster
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Forecaster for Naive {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        check_baseline_input(ts, 2, "Naive forecaster")?;
+
+        let diffs = ts.diff();
+        self.sigma2 = diffs.iter().map(|d| d * d).sum::<f64>() / diffs.len() as f64;
+        self.last_value = ts.values.last().copied();
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let last_value = self.last_value.ok_or_else(not_fitted)?;
+
+        Ok(ForecastResult {
+            predictions: vec![last_value; steps],
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.5,
+            intervals: Vec::new(),
+        })
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        let std_errors: Vec<f64> = (1..=steps).map(|h| (self.sigma2 * h as f64).sqrt()).collect();
+        ForecastResult::with_normal_interval(base_forecast, &std_errors, confidence_level)
+    }
+}
+
+/// Seasonal naive forecaster: each forecast repeats the observation one
+/// season earlier
+///
+/// Intervals use `σ·sqrt(k + 1)` where `k` is the number of complete seasons
+/// before horizon `h`, and σ² is the mean squared seasonal difference.
+#[derive(Debug, Clone)]
+pub struct SeasonalNaive {
+    period: usize,
+    last_season: Vec<f64>,
+    sigma2: f64,
+}
+
+impl SeasonalNaive {
+    /// Create a seasonal naive forecaster for season length `period`
+    pub fn new(period: usize) -> Result<Self> {
+        if period == 0 {
+            return Err(TelemetryError::InvalidParameter(
+                "Seasonal period must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            period,
+            last_season: Vec::new(),
+            sigma2: 0.0,
+        })
+    }
+}
+
+impl Forecaster for SeasonalNaive {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        check_baseline_input(ts, self.period + 1, "Seasonal naive forecaster")?;
+
+        let values = &ts.values;
+        let m = self.period;
+        self.sigma2 = values[m..]
+            .iter()
+            .zip(values.iter())
+            .map(|(cur, prev)| (cur - prev).powi(2))
+            .sum::<f64>()
+            / (values.len() - m) as f64;
+        self.last_season = values[values.len() - m..].to_vec();
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        if self.last_season.is_empty() {
+            return Err(not_fitted());
+        }
+
+        let predictions = (0..steps).map(|h| self.last_season[h % self.period]).collect();
+
+        Ok(ForecastResult {
+            predictions,
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.5,
+            intervals: Vec::new(),
+        })
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        let std_errors: Vec<f64> = (1..=steps)
+            .map(|h| (self.sigma2 * ((h - 1) / self.period + 1) as f64).sqrt())
+            .collect();
+        ForecastResult::with_normal_interval(base_forecast, &std_errors, confidence_level)
+    }
+}
+
+/// Drift forecaster: extends the line through the first and last
+/// observations
+///
+/// Intervals use `σ·sqrt(h(1 + h/(T - 1)))`, accounting for the estimated
+/// drift.
+#[derive(Debug, Clone, Default)]
+pub struct Drift {
+    last_value: Option<f64>,
+    slope: f64,
+    sigma2: f64,
+    n: usize,
+}
+
+impl Drift {
+    /// Create a new drift forecaster
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Estimated drift per step
+    pub fn slope(&self) -> f64 {
+        self.slope
+    }
+}
+
+impl Forecaster for Drift {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        check_baseline_input(ts, 3, "Drift forecaster")?;
+
+        let n = ts.len();
+        let values = &ts.values;
+        let slope = (values[n - 1] - values[0]) / (n - 1) as f64;
+        self.sigma2 = ts.diff().iter().map(|d| (d - slope).powi(2)).sum::<f64>() / (n - 2) as f64;
+        self.slope = slope;
+        self.last_value = Some(values[n - 1]);
+        self.n = n;
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let last_value = self.last_value.ok_or_else(not_fitted)?;
+        let predictions = (1..=steps).map(|h| last_value + self.slope * h as f64).collect();
+
+        Ok(ForecastResult {
+            predictions,
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.5,
+            intervals: Vec::new(),
+        })
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        let t = self.n as f64;
+        let std_errors: Vec<f64> = (1..=steps)
+            .map(|h| {
+                let h = h as f64;
+                (self.sigma2 * h * (1.0 + h / (t - 1.0))).sqrt()
+            })
+            .collect();
+        ForecastResult::with_normal_interval(base_forecast, &std_errors, confidence_level)
+    }
+}
+
+/// Seasonal component type for Holt-Winters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Seasonality {
+    /// Y = (level + trend) + season
+    Additive,
+    /// Y = (level + trend) * season
+    Multiplicative,
+}
+
+/// Smoothing parameters; `None` means "optimise by minimising SSE"
+#[derive(Debug, Clone, Copy)]
+struct SmoothingParams {
+    alpha: Option<f64>,
+    beta: Option<f64>,
+    gamma: Option<f64>,
+    phi: Option<f64>,
+    damped: bool,
+}
+
+impl SmoothingParams {
+    fn new(damped: bool) -> Self {
+        Self {
+            alpha: None,
+            beta: None,
+            gamma: None,
+            phi: None,
+            damped,
+        }
+    }
+}
+
+/// Final state of a fitted Holt / Holt-Winters recursion
+#[derive(Debug, Clone)]
+struct SmoothingState {
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    phi: f64,
+    level: f64,
+    trend: f64,
+    /// Last `period` seasonal indices, oldest first
+    seasonal: Vec<f64>,
+    sse: f64,
+    /// Sum of squared relative errors, tracked for multiplicative seasonality
+    relative_sse: f64,
+    n: usize,
+}
+
+impl SmoothingState {
+    /// Sum φ + φ² + … + φʰ
+    fn damped_sum(&self, h: usize) -> f64 {
+        (1..=h).map(|j| self.phi.powi(j as i32)).sum()
+    }
+
+    fn predictions(&self, steps: usize, seasonality: Option<Seasonality>) -> Vec<f64> {
+        (1..=steps)
+            .map(|h| {
+                let base = self.level + self.damped_sum(h) * self.trend;
+                match seasonality {
+                    None => base,
+                    Some(kind) => {
+                        let m = self.seasonal.len();
+                        let s = self.seasonal[(h - 1) % m];
+                        match kind {
+                            Seasonality::Additive => base + s,
+                            Seasonality::Multiplicative => base * s,
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Forecast variance multipliers `1 + Σ cⱼ²` for additive errors
+    fn variance_factors(&self, steps: usize, period: Option<usize>) -> Vec<f64> {
+        let mut factor = 1.0;
+        let mut factors = Vec::with_capacity(steps);
+        for j in 1..=steps {
+            factors.push(factor);
+            let mut c = self.alpha * (1.0 + self.beta * self.damped_sum(j));
+            if let Some(m) = period {
+                if j % m == 0 {
+                    c += self.gamma;
+                }
+            }
+            factor += c * c;
+        }
+        factors
+    }
+
+    /// Update the state with observation `y` at time `t` (seasonal position
+    /// `t % m`), returning the one-step prediction made before seeing it
+    fn update(&mut self, y: f64, t: usize, seasonality: Option<Seasonality>) -> f64 {
+        let base = self.level + self.phi * self.trend;
+        let (prediction, new_level) = match seasonality {
+            None => (base, self.alpha * y + (1.0 - self.alpha) * base),
+            Some(kind) => {
+                let i = t % self.seasonal.len();
+                let s_old = self.seasonal[i];
+                let (prediction, new_level, new_season) = match kind {
+                    Seasonality::Additive => (
+                        base + s_old,
+                        self.alpha * (y - s_old) + (1.0 - self.alpha) * base,
+                        self.gamma * (y - base) + (1.0 - self.gamma) * s_old,
+                    ),
+                    Seasonality::Multiplicative => (
+                        base * s_old,
+                        self.alpha * (y / s_old) + (1.0 - self.alpha) * base,
+                        self.gamma * (y / base) + (1.0 - self.gamma) * s_old,
+                    ),
+                };
+                self.seasonal[i] = new_season;
+                (prediction, new_level)
+            }
+        };
+        self.trend = self.beta * (new_level - self.level) + (1.0 - self.beta) * self.phi * self.trend;
+        self.level = new_level;
+        prediction
+    }
+
+    /// Interval quantiles of simulated sample paths with relative errors
+    /// `y = μ(1 + e)`, for the non-linear multiplicative-seasonal recursion
+    fn multiplicative_intervals(&self, n_params: usize, steps: usize, confidence_level: f64) -> (Vec<f64>, Vec<f64>) {
+        let sigma2 = self.relative_sse / self.dof(n_params);
+        simulate_intervals(self, sigma2, steps, confidence_level, |state, h, e| {
+            let m = state.seasonal.len();
+            let y = (state.level + state.phi * state.trend) * state.seasonal[h % m] * (1.0 + e);
+            state.update(y, h, Some(Seasonality::Multiplicative));
+            y
+        })
+    }
+
+    fn dof(&self, n_params: usize) -> f64 {
+        self.n.saturating_sub(n_params).max(1) as f64
+    }
+
+    fn sigma2(&self, n_params: usize) -> f64 {
+        self.sse / self.dof(n_params)
+    }
+}
+
+/// Run the (damped) Holt / Holt-Winters recursions with fixed parameters
+fn smooth(
+    values: &[f64],
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    phi: f64,
+    season: Option<(usize, Seasonality)>,
+) -> SmoothingState {
+    // Heuristic initial states: the first observation(s) for Holt, the first
+    // two seasons for Holt-Winters (recursion then starts after season one)
+    let (level, trend, seasonal, start) = match season {
+        None => {
+            let trend = values[1] - values[0];
+            (values[0] - trend, trend, Vec::new(), 0)
+        }
+        Some((m, kind)) => {
+            let first = values[..m].iter().sum::<f64>() / m as f64;
+            let second = values[m..2 * m].iter().sum::<f64>() / m as f64;
+            let trend = (second - first) / m as f64;
+            let seasonal = values[..m]
+                .iter()
+                .map(|&y| match kind {
+                    Seasonality::Additive => y - first,
+                    Seasonality::Multiplicative => y / first,
+                })
+                .collect();
+            (first + trend * (m as f64 - 1.0) / 2.0, trend, seasonal, m)
+        }
+    };
+
+    let mut state = SmoothingState {
+        alpha,
+        beta,
+        gamma,
+        phi,
+        level,
+        trend,
+        seasonal,
+        sse: 0.0,
+        relative_sse: 0.0,
+        n: 0,
+    };
+    let seasonality = season.map(|(_, kind)| kind);
+    for (i, &y) in values.iter().enumerate().skip(start) {
+        let prediction = state.update(y, i, seasonality);
+        let error = y - prediction;
+        state.sse += error * error;
+        if seasonality == Some(Seasonality::Multiplicative) {
+            state.relative_sse += (error / prediction).powi(2);
+        }
+        state.n += 1;
+    }
+
+    // Rotate so the seasonal indices start at the next period position
+    if let Some((m, _)) = season {
+        state.seasonal.rotate_left(values.len() % m);
+    }
+    state
+}
+
+/// Fit smoothing parameters not fixed by the user by minimising the SSE
+fn fit_smoothing(
+    values: &[f64],
+    params: &SmoothingParams,
+    season: Option<(usize, Seasonality)>,
+) -> SmoothingState {
+    let phi_bounds = (0.8, 0.98);
+    let free: Vec<(usize, f64)> = [
+        (0, params.alpha, 0.5),
+        (1, params.beta, 0.1),
+        (2, if season.is_some() { params.gamma } else { Some(0.0) }, 0.1),
+        (3, if params.damped { params.phi } else { Some(1.0) }, 0.9),
+    ]
+    .iter()
+    .filter(|(_, fixed, _)| fixed.is_none())
+    .map(|&(i, _, start)| (i, start))
+    .collect();
+
+    let unpack = |x: &[f64]| {
+        let mut p = [
+            params.alpha.unwrap_or(0.0),
+            params.beta.unwrap_or(0.0),
+            if season.is_some() { params.gamma.unwrap_or(0.0) } else { 0.0 },
+            if params.damped { params.phi.unwrap_or(0.0) } else { 1.0 },
+        ];
+        for (k, &(i, _)) in free.iter().enumerate() {
+            p[i] = if i == 3 {
+                to_interval(x[k], phi_bounds.0, phi_bounds.1)
+            } else {
+                to_interval(x[k], 0.0, 1.0)
+            };
+        }
+        p
+    };
+
+    let x0: Vec<f64> = free
+        .iter()
+        .map(|&(i, start)| {
+            if i == 3 {
+                from_interval(start, phi_bounds.0, phi_bounds.1)
+            } else {
+                from_interval(start, 0.0, 1.0)
+            }
+        })
+        .collect();
+
+    let objective = |x: &[f64]| {
+        let [a, b, g, phi] = unpack(x);
+        smooth(values, a, b, g, phi, season).sse
+    };
+    let (best, _) = nelder_mead(objective, &x0, 0.5, 1000, 1e-10);
+    let [a, b, g, phi] = unpack(&best);
+    smooth(values, a, b, g, phi, season)
+}
+
+fn check_unit_interval(name: &str, value: f64) -> Result<()> {
+    if value <= 0.0 || value > 1.0 {
+        return Err(TelemetryError::InvalidParameter(
+            format!("{} must be between 0 and 1", name),
+        ));
+    }
+    Ok(())
+}
+
+/// Intervals from additive-error variance factors
+fn smoothing_intervals(
+    base_forecast: ForecastResult,
+    state: &SmoothingState,
+    n_params: usize,
+    period: Option<usize>,
+    confidence_level: f64,
+) -> Result<ForecastResult> {
+    let sigma2 = state.sigma2(n_params);
+    let std_errors: Vec<f64> = state
+        .variance_factors(base_forecast.predictions.len(), period)
+        .iter()
+        .map(|f| (sigma2 * f).sqrt())
+        .collect();
+
+    ForecastResult::with_normal_interval(base_forecast, &std_errors, confidence_level)
+}
+
+/// Holt's linear trend method, optionally with a damped trend
+pub struct Holt {
+    params: SmoothingParams,
+    state: Option<SmoothingState>,
+}
+
+impl Default for Holt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Holt {
+    /// Create a Holt model with an additive trend; parameters are optimised on fit
+    pub fn new() -> Self {
+        Self {
+            params: SmoothingParams::new(false),
+            state: None,
+        }
+    }
+
+    /// Create a Holt model with a damped additive trend; parameters are optimised on fit
+    pub fn damped() -> Self {
+        Self {
+            params: SmoothingParams::new(true),
+            state: None,
+        }
+    }
+
+    /// Fix the level smoothing parameter
+    pub fn with_alpha(mut self, alpha: f64) -> Result<Self> {
+        check_unit_interval("Alpha", alpha)?;
+        self.params.alpha = Some(alpha);
+        Ok(self)
+    }
+
+    /// Fix the trend smoothing parameter
+    pub fn with_beta(mut self, beta: f64) -> Result<Self> {
+        check_unit_interval("Beta", beta)?;
+        self.params.beta = Some(beta);
+        Ok(self)
+    }
+
+    /// Fix the damping parameter (implies a damped trend)
+    pub fn with_phi(mut self, phi: f64) -> Result<Self> {
+        check_unit_interval("Phi", phi)?;
+        self.params.phi = Some(phi);
+        self.params.damped = true;
+        Ok(self)
+    }
+
+    /// Fitted (alpha, beta, phi)
+    pub fn parameters(&self) -> Option<(f64, f64, f64)> {
+        self.state.as_ref().map(|s| (s.alpha, s.beta, s.phi))
+    }
+
+    fn n_params(&self) -> usize {
+        4 + usize::from(self.params.damped)
+    }
+}
+
+impl Forecaster for Holt {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        if ts.len() < 3 {
+            return Err(TelemetryError::InsufficientData(
+                "Need at least 3 data points for Holt's method".to_string(),
+            ));
+        }
+        if ts.has_missing() {
+            return Err(TelemetryError::InvalidData(
+                "Holt's method cannot be fitted on a series with missing values".to_string(),
+            ));
+        }
+
+        self.state = Some(fit_smoothing(&ts.values, &self.params, None));
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let state = self.state.as_ref()
+            .ok_or_else(|| TelemetryError::ModelError(
+                "Model must be fitted before forecasting".to_string()
+            ))?;
+
+        Ok(ForecastResult {
+            predictions: state.predictions(steps, None),
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.7,
+            intervals: Vec::new(),
+        })
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        let state = self.state.as_ref().unwrap();
+        smoothing_intervals(base_forecast, state, self.n_params(), None, confidence_level)
+    }
+}
+
+/// Holt-Winters seasonal method with additive or multiplicative seasonality
+///
+/// Additive models have analytical interval variances; multiplicative models
+/// take interval quantiles from sample paths simulated with relative errors
+/// `y = μ(1 + e)`, so the width follows the seasonal index.
+pub struct HoltWinters {
+    period: usize,
+    seasonality: Seasonality,
+    params: SmoothingParams,
+    state: Option<SmoothingState>,
+}
+
+impl HoltWinters {
+    /// Create a Holt-Winters model; parameters are optimised on fit
+    pub fn new(period: usize, seasonality: Seasonality) -> Result<Self> {
+        if period < 2 {
+            return Err(TelemetryError::InvalidParameter(
+                "Seasonal period must be at least 2".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            period,
+            seasonality,
+            params: SmoothingParams::new(false),
+            state: None,
+        })
+    }
+
+    /// Use a damped trend
+    pub fn damped(mut self) -> Self {
+        self.params.damped = true;
+        self
+    }
+
+    /// Fix the level smoothing parameter
+    pub fn with_alpha(mut self, alpha: f64) -> Result<Self> {
+        check_unit_interval("Alpha", alpha)?;
+        self.params.alpha = Some(alpha);
+        Ok(self)
+    }
+
+    /// Fix the trend smoothing parameter
+    pub fn with_beta(mut self, beta: f64) -> Result<Self> {
+        check_unit_interval("Beta", beta)?;
+        self.params.beta = Some(beta);
+        Ok(self)
+    }
+
+    /// Fix the seasonal smoothing parameter
+    pub fn with_gamma(mut self, gamma: f64) -> Result<Self> {
+        check_unit_interval("Gamma", gamma)?;
+        self.params.gamma = Some(gamma);
+        Ok(self)
+    }
+
+    /// Fix the damping parameter (implies a damped trend)
+    pub fn with_phi(mut self, phi: f64) -> Result<Self> {
+        check_unit_interval("Phi", phi)?;
+        self.params.phi = Some(phi);
+        self.params.damped = true;
+        Ok(self)
+    }
+
+    /// Fitted (alpha, beta, gamma, phi)
+    pub fn parameters(&self) -> Option<(f64, f64, f64, f64)> {
+        self.state.as_ref().map(|s| (s.alpha, s.beta, s.gamma, s.phi))
+    }
+
+    fn n_params(&self) -> usize {
+        4 + self.period + usize::from(self.params.damped)
+    }
+}
+
+impl Forecaster for HoltWinters {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        if ts.len() < 2 * self.period {
+            return Err(TelemetryError::InsufficientData(
+                format!("Need at least {} data points for period {}", 2 * self.period, self.period),
+            ));
+        }
+        if ts.has_missing() {
+            return Err(TelemetryError::InvalidData(
+                "Holt-Winters cannot be fitted on a series with missing values".to_string(),
+            ));
+        }
+        if self.seasonality == Seasonality::Multiplicative && ts.values.iter().any(|&v| v <= 0.0) {
+            return Err(TelemetryError::InvalidData(
+                "Multiplicative seasonality requires strictly positive data".to_string(),
+            ));
+        }
+
+        let season = Some((self.period, self.seasonality));
+        self.state = Some(fit_smoothing(&ts.values, &self.params, season));
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let state = self.state.as_ref()
+            .ok_or_else(|| TelemetryError::ModelError(
+                "Model must be fitted before forecasting".to_string()
+            ))?;
+
+        Ok(ForecastResult {
+            predictions: state.predictions(steps, Some(self.seasonality)),
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.7,
+            intervals: Vec::new(),
+        })
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        let state = self.state.as_ref().unwrap();
+        if self.seasonality == Seasonality::Multiplicative {
+            // The additive variance factors do not apply to the non-linear
+            // recursion, so intervals come from simulated sample paths
+            z_score(confidence_level)?;
+            let (lower, upper) = state.multiplicative_intervals(self.n_params(), steps, confidence_level);
+            return Ok(ForecastResult::with_bounds(base_forecast, lower, upper, confidence_level));
+        }
+        let period = Some(self.period);
+        smoothing_intervals(base_forecast, state, self.n_params(), period, confidence_level)
+    }
 }
 
 #[cfg(test)]
@@ -225,5 +1139,121 @@ mod tests {
 
         let forecast = model.forecast(2).unwrap();
         assert_eq!(forecast.predictions.len(), 2);
+
+        let ci = model.forecast_with_confidence(4, 0.9).unwrap();
+        let width = |i: usize| ci.upper_bound.as_ref().unwrap()[i] - ci.lower_bound.as_ref().unwrap()[i];
+        assert!(width(3) > width(0));
+    }
+
+    #[test]
+    fn test_forecast_with_levels_nested_intervals() {
+        let data: Vec<f64> = (0..40).map(|i| 10.0 + (i as f64 * 0.7).sin()).collect();
+        let mut model = ExponentialSmoothing::new(0.4).unwrap();
+        model.fit(&TimeSeries::new(data)).unwrap();
+
+        let result = model.forecast_with_levels(3, &[0.8, 0.5, 0.99]).unwrap();
+        assert_eq!(result.intervals.len(), 3);
+        assert_eq!(result.confidence, 0.8);
+
+        let width = |level: f64| {
+            let i = result.interval(level).unwrap();
+            i.upper[0] - i.lower[0]
+        };
+        assert!(width(0.5) < width(0.8) && width(0.8) < width(0.99));
+        // Exact normal quantiles: z(0.99) / z(0.8) = 2.5758 / 1.2816
+        assert!((width(0.99) / width(0.8) - 2.575829 / 1.281552).abs() < 1e-5);
+
+        assert!(model.forecast_with_levels(3, &[]).is_err());
+        assert!(model.forecast_with_confidence(3, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_baseline_forecasters() {
+        let data = vec![1.0, 5.0, 3.0, 2.0, 6.0, 4.0, 3.0, 7.0, 5.0];
+        let ts = TimeSeries::new(data);
+
+        let mut mean = Mean::new();
+        mean.fit(&ts).unwrap();
+        let ci = mean.forecast_with_confidence(3, 0.95).unwrap();
+        assert!((ci.predictions[0] - 4.0).abs() < 1e-12);
+        let widths: Vec<f64> = ci.intervals[0].upper.iter().zip(&ci.intervals[0].lower).map(|(u, l)| u - l).collect();
+        assert!((widths[0] - widths[2]).abs() < 1e-12);
+
+        let mut naive = Naive::new();
+        naive.fit(&ts).unwrap();
+        let ci = naive.forecast_with_confidence(4, 0.9).unwrap();
+        assert_eq!(ci.predictions, vec![5.0; 4]);
+        let (lower, upper) = (ci.lower_bound.unwrap(), ci.upper_bound.unwrap());
+        assert!(((upper[3] - lower[3]) / (upper[0] - lower[0]) - 2.0).abs() < 1e-12);
+
+        let mut snaive = SeasonalNaive::new(3).unwrap();
+        snaive.fit(&ts).unwrap();
+        let ci = snaive.forecast_with_confidence(4, 0.8).unwrap();
+        assert_eq!(ci.predictions, vec![3.0, 7.0, 5.0, 3.0]);
+        // Seasonal differences are all +1, so sigma2 = 1; h = 4 spans two seasons
+        let (lower, upper) = (ci.lower_bound.unwrap(), ci.upper_bound.unwrap());
+        assert!((upper[2] - lower[2] - 2.0 * 1.281552).abs() < 1e-5);
+        assert!((upper[3] - lower[3] - 2.0 * 1.281552 * 2.0f64.sqrt()).abs() < 1e-5);
+
+        let mut drift = Drift::new();
+        drift.fit(&TimeSeries::new((0..10).map(|x| 3.0 + 0.5 * x as f64).collect())).unwrap();
+        let forecast = drift.forecast(2).unwrap();
+        assert!((forecast.predictions[1] - 8.5).abs() < 1e-12);
+        assert!((drift.slope() - 0.5).abs() < 1e-12);
+
+        assert!(SeasonalNaive::new(0).is_err());
+        assert!(Naive::new().forecast(1).is_err());
+        assert!(mean.fit(&TimeSeries::new(vec![1.0, f64::NAN, 2.0])).is_err());
+    }
+
+    #[test]
+    fn test_holt_follows_trend() {
+        let ts = TimeSeries::new((1..=30).map(|x| x as f64).collect());
+
+        let mut model = Holt::new();
+        model.fit(&ts).unwrap();
+
+        let forecast = model.forecast(3).unwrap();
+        assert!((forecast.predictions[0] - 31.0).abs() < 1e-3);
+        assert!((forecast.predictions[2] - 33.0).abs() < 1e-3);
+
+        let mut damped = Holt::damped();
+        damped.fit(&ts).unwrap();
+        let damped_forecast = damped.forecast(10).unwrap();
+        assert!(damped_forecast.predictions[9] < 40.0);
+        assert!(damped_forecast.predictions[9] > 30.0);
+    }
+
+    #[test]
+    fn test_holt_winters_seasonal_patterns() {
+        let pattern = [1.2, 0.8, 1.1, 0.9];
+        let data: Vec<f64> = (0..40)
+            .map(|i| (50.0 + i as f64) * pattern[i % 4])
+            .collect();
+        let ts = TimeSeries::new(data);
+
+        let mut multiplicative = HoltWinters::new(4, Seasonality::Multiplicative).unwrap();
+        multiplicative.fit(&ts).unwrap();
+        let forecast = multiplicative.forecast(4).unwrap();
+        for (h, pred) in forecast.predictions.iter().enumerate() {
+            let expected = (90.0 + h as f64) * pattern[h % 4];
+            assert!((pred - expected).abs() / expected < 0.05);
+        }
+
+        let mut additive = HoltWinters::new(4, Seasonality::Additive).unwrap();
+        additive.fit(&ts).unwrap();
+        let ci = additive.forecast_with_confidence(8, 0.95).unwrap();
+        let width = |i: usize| ci.upper_bound.as_ref().unwrap()[i] - ci.lower_bound.as_ref().unwrap()[i];
+        assert!(width(7) >= width(0));
+
+        // Multiplicative intervals scale with the seasonal index: h = 1 falls
+        // on the 1.2 peak, h = 2 on the 0.8 trough, so widths differ by 1.5×
+        let noisy: Vec<f64> = ts.values.iter().enumerate().map(|(i, v)| v + ((i * 7) % 5) as f64 - 2.0).collect();
+        multiplicative.fit(&TimeSeries::new(noisy)).unwrap();
+        let ci = multiplicative.forecast_with_confidence(8, 0.9).unwrap();
+        let width = |i: usize| ci.upper_bound.as_ref().unwrap()[i] - ci.lower_bound.as_ref().unwrap()[i];
+        let ratio = width(0) / width(1);
+        assert!((ratio - 1.2 / 0.8).abs() < 0.1, "width ratio {}", ratio);
+        assert!(width(4) > width(0));
     }
 }
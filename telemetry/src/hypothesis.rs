@@ -0,0 +1,623 @@
+//! Statistical hypothesis tests for time series
+//!
+//! Unit-root tests (ADF, Phillips-Perron) have a unit root as null
+//! hypothesis, while KPSS has stationarity as null, so the two kinds are
+//! usually read together. OCSB tests for a seasonal unit root. Ljung-Box, Box-Pierce, Jarque-Bera and ARCH-LM
+//! check residuals for autocorrelation, non-normality and conditional
+//! heteroscedasticity. Dickey-Fuller p-values use MacKinnon's (1994)
+//! response surfaces and critical values MacKinnon (2010); KPSS p-values are
+//! interpolated in the Kwiatkowski et al. (1992) table and bounded to
+//! [0.01, 0.10].
+
+use statrs::distribution::{ChiSquared, ContinuousCDF, Normal};
+
+use crate::correlation::acf;
+use crate::linalg::{least_squares, solve};
+use crate::{Result, TelemetryError, TimeSeries};
+
+/// Deterministic terms included in a test regression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deterministic {
+    /// No constant or trend
+    None,
+    /// Constant (level)
+    Constant,
+    /// Constant and linear time trend
+    Trend,
+}
+
+impl Deterministic {
+    fn terms(&self) -> usize {
+        match self {
+            Deterministic::None => 0,
+            Deterministic::Constant => 1,
+            Deterministic::Trend => 2,
+        }
+    }
+
+    /// Constant and trend regressors for time index `t`
+    fn regressors(&self, t: usize) -> Vec<f64> {
+        match self {
+            Deterministic::None => vec![],
+            Deterministic::Constant => vec![1.0],
+            Deterministic::Trend => vec![1.0, t as f64],
+        }
+    }
+}
+
+/// Outcome of a hypothesis test
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    /// Test statistic
+    pub statistic: f64,
+    /// p-value of the statistic under the null hypothesis
+    pub p_value: f64,
+    /// Lag order, bandwidth or number of autocorrelations used
+    pub lags: usize,
+    /// Number of observations in the test regression or sum
+    pub nobs: usize,
+    /// `(level, critical value)` pairs for tests with a non-standard null
+    /// distribution; empty otherwise
+    pub critical_values: Vec<(f64, f64)>,
+}
+
+impl TestResult {
+    /// Whether the null hypothesis is rejected at significance `alpha`
+    pub fn rejects(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+fn finite_values(ts: &TimeSeries, min_len: usize) -> Result<&[f64]> {
+    if ts.values.iter().any(|v| !v.is_finite()) {
+        return Err(TelemetryError::InvalidData(
+            "Hypothesis tests require finite values; fill missing values first".to_string(),
+        ));
+    }
+    if ts.len() < min_len {
+        return Err(TelemetryError::InsufficientData(
+            format!("Need at least {} data points", min_len),
+        ));
+    }
+    Ok(&ts.values)
+}
+
+/// OLS fit with residual sum of squares and coefficient standard errors
+struct Regression {
+    beta: Vec<f64>,
+    residuals: Vec<f64>,
+    rss: f64,
+}
+
+impl Regression {
+    fn fit(rows: &[Vec<f64>], y: &[f64]) -> Result<Self> {
+        let beta = least_squares(rows, y)?;
+        let residuals: Vec<f64> = rows
+            .iter()
+            .zip(y)
+            .map(|(row, yi)| yi - row.iter().zip(&beta).map(|(x, b)| x * b).sum::<f64>())
+            .collect();
+        let rss = residuals.iter().map(|e| e * e).sum();
+        Ok(Self { beta, residuals, rss })
+    }
+
+    /// Standard error of coefficient `j` given the residual variance
+    fn std_error(&self, rows: &[Vec<f64>], j: usize, sigma2: f64) -> Result<f64> {
+        let k = self.beta.len();
+        let mut xtx = vec![vec![0.0; k]; k];
+        for row in rows {
+            for a in 0..k {
+                for b in 0..k {
+                    xtx[a][b] += row[a] * row[b];
+                }
+            }
+        }
+        let mut unit = vec![0.0; k];
+        unit[j] = 1.0;
+        Ok((sigma2 * solve(&xtx, &unit)?[j]).sqrt())
+    }
+}
+
+/// Approximate p-value of a Dickey-Fuller t-statistic (MacKinnon 1994)
+fn mackinnon_p(tau: f64, deterministic: Deterministic) -> f64 {
+    let (max, min, star, small, large) = match deterministic {
+        Deterministic::None => (f64::INFINITY, -19.04, -1.04, [0.6344, 1.2378, 3.2496e-2], [0.4797, 0.93557, -0.06999, 0.033066]),
+        Deterministic::Constant => (2.74, -18.83, -1.61, [2.1659, 1.4412, 3.8269e-2], [1.7339, 0.93202, -0.12745, -0.010368]),
+        Deterministic::Trend => (0.7, -16.18, -2.89, [3.2512, 1.6047, 4.9588e-2], [2.5261, 0.61654, -0.37956, -0.060285]),
+    };
+    if tau > max {
+        return 1.0;
+    }
+    if tau < min {
+        return 0.0;
+    }
+
+    let z = if tau <= star {
+        small[0] + small[1] * tau + small[2] * tau * tau
+    } else {
+        large[0] + large[1] * tau + large[2] * tau * tau + large[3] * tau.powi(3)
+    };
+    Normal::new(0.0, 1.0).unwrap().cdf(z)
+}
+
+/// Dickey-Fuller critical values at 1%, 5% and 10% for `nobs` observations
+/// (MacKinnon 2010)
+fn dickey_fuller_critical(deterministic: Deterministic, nobs: usize) -> Vec<(f64, f64)> {
+    let coefficients: [[f64; 4]; 3] = match deterministic {
+        Deterministic::None => [
+            [-2.56574, -2.2358, -3.627, 0.0],
+            [-1.94100, -0.2686, -3.365, 31.223],
+            [-1.61682, 0.2656, -2.714, 25.364],
+        ],
+        Deterministic::Constant => [
+            [-3.43035, -6.5393, -16.786, -79.433],
+            [-2.86154, -2.8903, -4.234, -40.040],
+            [-2.56677, -1.5384, -2.809, 0.0],
+        ],
+        Deterministic::Trend => [
+            [-3.95877, -9.0531, -28.428, -134.155],
+            [-3.41049, -4.3904, -9.036, -45.374],
+            [-3.12705, -2.5856, -3.925, -22.380],
+        ],
+    };
+    let t = nobs as f64;
+    [0.01, 0.05, 0.10]
+        .iter()
+        .zip(coefficients)
+        .map(|(&level, c)| (level, c[0] + c[1] / t + c[2] / (t * t) + c[3] / t.powi(3)))
+        .collect()
+}
+
+/// Newey-West long-run variance of residuals with Bartlett weights
+fn long_run_variance(e: &[f64], lags: usize) -> f64 {
+    let n = e.len() as f64;
+    let mut variance = e.iter().map(|v| v * v).sum::<f64>() / n;
+    for lag in 1..=lags.min(e.len() - 1) {
+        let weight = 1.0 - lag as f64 / (lags as f64 + 1.0);
+        let cov: f64 = e[lag..].iter().zip(e).map(|(a, b)| a * b).sum::<f64>() / n;
+        variance += 2.0 * weight * cov;
+    }
+    variance
+}
+
+/// Augmented Dickey-Fuller test; null hypothesis: the series has a unit root
+///
+/// With `lags = None` the number of lagged differences is chosen by AIC up to
+/// `12 * (n / 100)^(1/4)`, capped so the regression keeps at least half the
+/// series. An explicit `lags` beyond that cap is rejected.
+pub fn adf(ts: &TimeSeries, deterministic: Deterministic, lags: Option<usize>) -> Result<TestResult> {
+    let y = finite_values(ts, 8)?;
+    let n = y.len();
+    let dy: Vec<f64> = y.windows(2).map(|w| w[1] - w[0]).collect();
+
+    let schwert = (12.0 * (n as f64 / 100.0).powf(0.25)).floor() as usize;
+    let cap = (n / 2).saturating_sub(deterministic.terms() + 2);
+    if let Some(k) = lags.filter(|&k| k > cap) {
+        return Err(TelemetryError::InvalidParameter(
+            format!("ADF lag order {} exceeds the maximum of {} for {} data points", k, cap, n),
+        ));
+    }
+    let max_lag = lags.unwrap_or(schwert).min(cap);
+
+    // Rows t..dy.len() regress dy[t] on the level y[t] and p lagged differences
+    let design = |p: usize, start: usize| -> (Vec<Vec<f64>>, Vec<f64>) {
+        let rows = (start..dy.len())
+            .map(|t| {
+                let mut row = vec![y[t]];
+                row.extend(deterministic.regressors(t + 1));
+                row.extend((1..=p).map(|i| dy[t - i]));
+                row
+            })
+            .collect();
+        (rows, dy[start..].to_vec())
+    };
+
+    let p = match lags {
+        Some(_) => max_lag,
+        None => {
+            let mut best = (f64::INFINITY, 0);
+            for p in 0..=max_lag {
+                let (rows, target) = design(p, max_lag);
+                let fit = Regression::fit(&rows, &target)?;
+                let nobs = target.len() as f64;
+                let aic = nobs * (fit.rss / nobs).ln() + 2.0 * rows[0].len() as f64;
+                if aic < best.0 {
+                    best = (aic, p);
+                }
+            }
+            best.1
+        }
+    };
+
+    let (rows, target) = design(p, p);
+    let fit = Regression::fit(&rows, &target)?;
+    let nobs = target.len();
+    let sigma2 = fit.rss / (nobs - rows[0].len()) as f64;
+    let tau = fit.beta[0] / fit.std_error(&rows, 0, sigma2)?;
+
+    Ok(TestResult {
+        statistic: tau,
+        p_value: mackinnon_p(tau, deterministic),
+        lags: p,
+        nobs,
+        critical_values: dickey_fuller_critical(deterministic, nobs),
+    })
+}
+
+/// Phillips-Perron Z(t) test; null hypothesis: the series has a unit root
+///
+/// Corrects the Dickey-Fuller t-statistic for serial correlation with a
+/// Newey-West long-run variance instead of lagged differences. `lags`
+/// defaults to `ceil(12 * (n / 100)^(1/4))`.
+pub fn phillips_perron(ts: &TimeSeries, deterministic: Deterministic, lags: Option<usize>) -> Result<TestResult> {
+    let y = finite_values(ts, 8)?;
+    let n = y.len();
+    let lags = lags.unwrap_or_else(|| (12.0 * (n as f64 / 100.0).powf(0.25)).ceil() as usize);
+
+    let rows: Vec<Vec<f64>> = (1..n)
+        .map(|t| {
+            let mut row = vec![y[t - 1]];
+            row.extend(deterministic.regressors(t));
+            row
+        })
+        .collect();
+    let fit = Regression::fit(&rows, &y[1..])?;
+
+    let nobs = n - 1;
+    let t = nobs as f64;
+    let sigma2 = fit.rss / (nobs - rows[0].len()) as f64;
+    let se = fit.std_error(&rows, 0, sigma2)?;
+    let t_rho = (fit.beta[0] - 1.0) / se;
+
+    let gamma0 = fit.rss / t;
+    let lambda2 = long_run_variance(&fit.residuals, lags);
+    let z_tau = (gamma0 / lambda2).sqrt() * t_rho
+        - (lambda2 - gamma0) / (2.0 * lambda2.sqrt()) * (t * se / sigma2.sqrt());
+
+    Ok(TestResult {
+        statistic: z_tau,
+        p_value: mackinnon_p(z_tau, deterministic),
+        lags,
+        nobs,
+        critical_values: dickey_fuller_critical(deterministic, nobs),
+    })
+}
+
+/// KPSS test; null hypothesis: the series is level- (`Constant`) or
+/// trend-stationary (`Trend`)
+///
+/// `lags` defaults to `floor(3 * sqrt(n) / 13)`. The p-value is interpolated
+/// in the table of critical values and bounded to [0.01, 0.10].
+pub fn kpss(ts: &TimeSeries, deterministic: Deterministic, lags: Option<usize>) -> Result<TestResult> {
+    let y = finite_values(ts, 4)?;
+    let n = y.len();
+    let table: [(f64, f64); 4] = match deterministic {
+        Deterministic::None => {
+            return Err(TelemetryError::InvalidParameter(
+                "KPSS needs a constant or a trend".to_string(),
+            ));
+        }
+        Deterministic::Constant => [(0.10, 0.347), (0.05, 0.463), (0.025, 0.574), (0.01, 0.739)],
+        Deterministic::Trend => [(0.10, 0.119), (0.05, 0.146), (0.025, 0.176), (0.01, 0.216)],
+    };
+    let lags = lags.unwrap_or_else(|| (3.0 * (n as f64).sqrt() / 13.0).floor() as usize);
+
+    let rows: Vec<Vec<f64>> = (0..n).map(|t| deterministic.regressors(t)).collect();
+    let e = Regression::fit(&rows, y)?.residuals;
+
+    let mut partial = 0.0;
+    let eta = e
+        .iter()
+        .map(|v| {
+            partial += v;
+            partial * partial
+        })
+        .sum::<f64>()
+        / (n * n) as f64;
+    let long_run = long_run_variance(&e, lags);
+    let statistic = if long_run <= 0.0 { 0.0 } else { eta / long_run };
+    if !statistic.is_finite() {
+        return Err(TelemetryError::InvalidData(
+            "KPSS statistic is not finite; rescale the series".to_string(),
+        ));
+    }
+
+    let p_value = if statistic <= table[0].1 {
+        table[0].0
+    } else if statistic >= table[3].1 {
+        table[3].0
+    } else {
+        let i = table.windows(2).position(|w| statistic < w[1].1).unwrap();
+        let ((p0, c0), (p1, c1)) = (table[i], table[i + 1]);
+        p0 + (p1 - p0) * (statistic - c0) / (c1 - c0)
+    };
+
+    Ok(TestResult {
+        statistic,
+        p_value,
+        lags,
+        nobs: n,
+        critical_values: table.iter().rev().copied().collect(),
+    })
+}
+
+/// Osborn-Chui-Smith-Birchenhall test; null hypothesis: the series has a
+/// seasonal unit root at `period`
+///
+/// Regresses `ΔΔₘyₜ` on `Δₘyₜ₋₁`, `Δyₜ₋ₘ` and lagged `ΔΔₘyₜ`; the statistic
+/// is the t-ratio of `Δyₜ₋ₘ`, and small values reject the null. With
+/// `lags = None` up to three lags are chosen by AIC. Only the 5% critical
+/// value is tabulated (the response surface in `ln m` used by R's
+/// `forecast::nsdiffs`), so the p-value is a normal approximation centred
+/// on it: `rejects(0.05)` is exact, other levels are indicative.
+pub fn ocsb(ts: &TimeSeries, period: usize, lags: Option<usize>) -> Result<TestResult> {
+    if period < 2 {
+        return Err(TelemetryError::InvalidParameter(
+            "Seasonal period must be at least 2".to_string(),
+        ));
+    }
+    let y = finite_values(ts, 2 * period + 8)?;
+    let n = y.len();
+    let m = period;
+
+    // Keep at least eight residual degrees of freedom
+    let cap = (n - m - 1).saturating_sub(10) / 2;
+    if let Some(k) = lags.filter(|&k| k > cap) {
+        return Err(TelemetryError::InvalidParameter(
+            format!("OCSB lag order {} exceeds the maximum of {} for {} data points", k, cap, n),
+        ));
+    }
+    let max_lag = lags.unwrap_or(3).min(cap);
+
+    // (1 - B)(1 - Bᵐ)y at time t, defined from t = m + 1
+    let dd = |t: usize| y[t] - y[t - m] - y[t - 1] + y[t - m - 1];
+    let design = |p: usize, start: usize| -> (Vec<Vec<f64>>, Vec<f64>) {
+        let rows = (start..n)
+            .map(|t| {
+                let mut row = vec![y[t - 1] - y[t - 1 - m], y[t - m] - y[t - m - 1]];
+                row.extend((1..=p).map(|i| dd(t - i)));
+                row
+            })
+            .collect();
+        (rows, (start..n).map(dd).collect())
+    };
+
+    let p = match lags {
+        Some(_) => max_lag,
+        None => {
+            let mut best = (f64::INFINITY, 0);
+            for p in 0..=max_lag {
+                let (rows, target) = design(p, m + 1 + max_lag);
+                let fit = Regression::fit(&rows, &target)?;
+                let nobs = target.len() as f64;
+                let aic = nobs * (fit.rss / nobs).ln() + 2.0 * rows[0].len() as f64;
+                if aic < best.0 {
+                    best = (aic, p);
+                }
+            }
+            best.1
+        }
+    };
+
+    let (rows, target) = design(p, m + 1 + p);
+    let fit = Regression::fit(&rows, &target)?;
+    let nobs = target.len();
+    let sigma2 = fit.rss / (nobs - rows[0].len()) as f64;
+    let statistic = fit.beta[1] / fit.std_error(&rows, 1, sigma2)?;
+    if !statistic.is_finite() {
+        return Err(TelemetryError::InvalidData(
+            "OCSB statistic is not finite; the series may be exactly periodic".to_string(),
+        ));
+    }
+
+    let log_m = (m as f64).ln() - 0.7656451;
+    let critical = -0.2937411 * (-0.2850853 * log_m - 0.05983644 * log_m * log_m).exp() - 1.652202;
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let p_value = normal.cdf(statistic - critical + normal.inverse_cdf(0.05));
+
+    Ok(TestResult {
+        statistic,
+        p_value,
+        lags: p,
+        nobs,
+        critical_values: vec![(0.05, critical)],
+    })
+}
+
+fn portmanteau(ts: &TimeSeries, lags: usize, fitted_params: usize, ljung: bool) -> Result<TestResult> {
+    if lags <= fitted_params {
+        return Err(TelemetryError::InvalidParameter(
+            "Number of lags must exceed the number of fitted parameters".to_string(),
+        ));
+    }
+    let n = finite_values(ts, lags + 1)?.len() as f64;
+    let r = acf(ts, lags, 0.95)?.values;
+
+    let statistic = if ljung {
+        n * (n + 2.0) * r.iter().enumerate().map(|(i, rk)| rk * rk / (n - (i + 1) as f64)).sum::<f64>()
+    } else {
+        n * r.iter().map(|rk| rk * rk).sum::<f64>()
+    };
+    let chi2 = ChiSquared::new((lags - fitted_params) as f64).unwrap();
+
+    Ok(TestResult {
+        statistic,
+        p_value: 1.0 - chi2.cdf(statistic),
+        lags,
+        nobs: n as usize,
+        critical_values: Vec::new(),
+    })
+}
+
+/// Ljung-Box test; null hypothesis: no autocorrelation up to `lags`
+///
+/// For model residuals, `fitted_params` (e.g. `p + q` for ARMA) is
+/// subtracted from the degrees of freedom.
+pub fn ljung_box(ts: &TimeSeries, lags: usize, fitted_params: usize) -> Result<TestResult> {
+    portmanteau(ts, lags, fitted_params, true)
+}
+
+/// Box-Pierce test; the large-sample form of [`ljung_box`]
+pub fn box_pierce(ts: &TimeSeries, lags: usize, fitted_params: usize) -> Result<TestResult> {
+    portmanteau(ts, lags, fitted_params, false)
+}
+
+/// Jarque-Bera test; null hypothesis: skewness 0 and kurtosis 3 (normality)
+pub fn jarque_bera(ts: &TimeSeries) -> Result<TestResult> {
+    let x = finite_values(ts, 3)?;
+    let n = x.len() as f64;
+    let mean = x.iter().sum::<f64>() / n;
+    let moment = |k: i32| x.iter().map(|v| (v - mean).powi(k)).sum::<f64>() / n;
+    let m2 = moment(2);
+    if m2 <= 0.0 {
+        return Err(TelemetryError::InvalidData(
+            "Series is constant".to_string(),
+        ));
+    }
+
+    let skewness = moment(3) / m2.powf(1.5);
+    let kurtosis = moment(4) / (m2 * m2);
+    let statistic = n / 6.0 * (skewness * skewness + (kurtosis - 3.0).powi(2) / 4.0);
+
+    Ok(TestResult {
+        statistic,
+        p_value: 1.0 - ChiSquared::new(2.0).unwrap().cdf(statistic),
+        lags: 0,
+        nobs: x.len(),
+        critical_values: Vec::new(),
+    })
+}
+
+/// Engle's ARCH-LM test; null hypothesis: no ARCH effects up to `lags`
+///
+/// Regresses squared deviations on their own lags; `nobs * R²` is
+/// chi-squared with `lags` degrees of freedom.
+pub fn arch_lm(ts: &TimeSeries, lags: usize) -> Result<TestResult> {
+    if lags == 0 {
+        return Err(TelemetryError::InvalidParameter(
+            "Need at least one lag".to_string(),
+        ));
+    }
+    let x = finite_values(ts, 2 * lags + 2)?;
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    let e2: Vec<f64> = x.iter().map(|v| (v - mean).powi(2)).collect();
+
+    let rows: Vec<Vec<f64>> = (lags..e2.len())
+        .map(|t| std::iter::once(1.0).chain((1..=lags).map(|i| e2[t - i])).collect())
+        .collect();
+    let target = &e2[lags..];
+    let fit = Regression::fit(&rows, target)?;
+
+    let nobs = target.len();
+    let target_mean = target.iter().sum::<f64>() / nobs as f64;
+    let tss: f64 = target.iter().map(|v| (v - target_mean).powi(2)).sum();
+    let r_squared = if tss > 0.0 { 1.0 - fit.rss / tss } else { 0.0 };
+    let statistic = nobs as f64 * r_squared;
+
+    Ok(TestResult {
+        statistic,
+        p_value: 1.0 - ChiSquared::new(lags as f64).unwrap().cdf(statistic),
+        lags,
+        nobs,
+        critical_values: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::shocks;
+
+    fn random_walk(n: usize) -> TimeSeries {
+        let mut level = 0.0;
+        TimeSeries::new(shocks(n).into_iter().map(|e| {
+            level += e;
+            level
+        }).collect())
+    }
+
+    #[test]
+    fn test_unit_root_tests() {
+        let noise = TimeSeries::new(shocks(300));
+        let walk = random_walk(300);
+
+        let stationary = adf(&noise, Deterministic::Constant, None).unwrap();
+        assert!(stationary.rejects(0.01));
+        assert!(stationary.statistic < stationary.critical_values[0].1);
+        assert!(!adf(&walk, Deterministic::Constant, None).unwrap().rejects(0.10));
+        assert_eq!(adf(&walk, Deterministic::Trend, Some(4)).unwrap().lags, 4);
+        assert!(adf(&walk, Deterministic::Trend, Some(200)).is_err());
+
+        assert!(phillips_perron(&noise, Deterministic::Constant, None).unwrap().rejects(0.01));
+        assert!(!phillips_perron(&walk, Deterministic::Trend, None).unwrap().rejects(0.10));
+
+        // p-values at the asymptotic 5% critical values
+        assert!((mackinnon_p(-2.86, Deterministic::Constant) - 0.05).abs() < 0.005);
+        assert!((mackinnon_p(-3.41, Deterministic::Trend) - 0.05).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_kpss() {
+        let noise = TimeSeries::new(shocks(300));
+        let level = kpss(&noise, Deterministic::Constant, None).unwrap();
+        assert_eq!(level.p_value, 0.10);
+        assert!(!level.rejects(0.05));
+
+        let walk = random_walk(300);
+        assert!(kpss(&walk, Deterministic::Constant, None).unwrap().rejects(0.05));
+
+        let trending = TimeSeries::new(shocks(300).iter().enumerate().map(|(t, e)| 0.2 * t as f64 + e).collect());
+        assert!(kpss(&trending, Deterministic::Constant, None).unwrap().rejects(0.05));
+        assert!(!kpss(&trending, Deterministic::Trend, None).unwrap().rejects(0.05));
+        assert!(kpss(&noise, Deterministic::None, None).is_err());
+        let huge = TimeSeries::new((0..50).map(|t| if t % 2 == 0 { 1e308 } else { -1e308 }).collect());
+        assert!(kpss(&huge, Deterministic::Constant, None).is_err());
+    }
+
+    #[test]
+    fn test_ocsb() {
+        let e = shocks(240);
+        let mut seasonal_walk = e[..12].to_vec();
+        for t in 12..e.len() {
+            seasonal_walk.push(seasonal_walk[t - 12] + e[t]);
+        }
+        let walk = ocsb(&TimeSeries::new(seasonal_walk), 12, None).unwrap();
+        assert!((walk.critical_values[0].1 + 1.803).abs() < 0.001);
+        assert!(!walk.rejects(0.05));
+
+        let noise = ocsb(&TimeSeries::new(e), 12, None).unwrap();
+        assert!(noise.rejects(0.05));
+        assert!(noise.statistic < noise.critical_values[0].1);
+        assert!(ocsb(&random_walk(240), 12, Some(200)).is_err());
+        assert!(ocsb(&random_walk(20), 12, None).is_err());
+    }
+
+    #[test]
+    fn test_residual_diagnostics() {
+        let e = shocks(500);
+        let noise = TimeSeries::new(e.clone());
+        let lb = ljung_box(&noise, 10, 0).unwrap();
+        assert!(!lb.rejects(0.05));
+        assert!(box_pierce(&noise, 10, 0).unwrap().statistic < lb.statistic);
+        assert!(!jarque_bera(&noise).unwrap().rejects(0.05));
+        assert!(!arch_lm(&noise, 4).unwrap().rejects(0.05));
+
+        let mut ar = vec![e[0]];
+        for t in 1..e.len() {
+            ar.push(0.6 * ar[t - 1] + e[t]);
+        }
+        assert!(ljung_box(&TimeSeries::new(ar), 10, 0).unwrap().rejects(0.001));
+
+        let skewed = TimeSeries::new(e.iter().map(|v| v.exp()).collect());
+        assert!(jarque_bera(&skewed).unwrap().rejects(0.001));
+
+        let mut arch = vec![e[0]];
+        for t in 1..e.len() {
+            let prev: f64 = arch[t - 1];
+            arch.push(e[t] * (0.2 + 0.7 * prev * prev).sqrt());
+        }
+        assert!(arch_lm(&TimeSeries::new(arch), 4).unwrap().rejects(0.001));
+
+        assert!(ljung_box(&noise, 2, 2).is_err());
+    }
+}
@@ -0,0 +1,339 @@
+//! Intermittent-demand forecasting
+//!
+//! Croston's method and its Syntetos-Boylan (SBA) bias correction smooth the
+//! non-zero demand sizes and the intervals between them separately; the
+//! Teunter-Syntetos-Babai (TSB) method smooths the demand probability every
+//! period instead, so forecasts decay when demand stops. [`classify_demand`]
+//! places a series in the ADI/CV² scheme to decide whether these methods
+//! are appropriate.
+
+use crate::{Result, TelemetryError, TimeSeries};
+use crate::forecasting::{z_score, ForecastResult, Forecaster};
+use crate::time_series::quantile_sorted;
+
+/// Average demand interval above which demand counts as intermittent
+pub const ADI_CUTOFF: f64 = 1.32;
+
+/// Squared coefficient of variation of demand sizes above which demand
+/// counts as erratic
+pub const CV2_CUTOFF: f64 = 0.49;
+
+/// Demand pattern in the Syntetos-Boylan ADI/CV² classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DemandPattern {
+    /// Regular demand with stable sizes; continuous methods (SES, ETS) work well
+    Smooth,
+    /// Regular demand with variable sizes
+    Erratic,
+    /// Sporadic demand with stable sizes; use Croston, SBA or TSB
+    Intermittent,
+    /// Sporadic demand with variable sizes; use SBA or TSB
+    Lumpy,
+}
+
+impl DemandPattern {
+    /// Whether an intermittent-demand method (Croston, SBA, TSB) should be
+    /// preferred over a continuous one
+    pub fn is_intermittent(&self) -> bool {
+        matches!(self, DemandPattern::Intermittent | DemandPattern::Lumpy)
+    }
+}
+
+/// Result of [`classify_demand`]
+#[derive(Debug, Clone, Copy)]
+pub struct DemandClassification {
+    /// Average number of periods per non-zero demand
+    pub adi: f64,
+    /// Squared coefficient of variation of the non-zero demand sizes
+    pub cv2: f64,
+    /// Resulting pattern
+    pub pattern: DemandPattern,
+}
+
+/// Classify a demand series by its average demand interval and the
+/// variability of its non-zero demand sizes
+pub fn classify_demand(ts: &TimeSeries) -> Result<DemandClassification> {
+    check_demand(ts)?;
+
+    let sizes: Vec<f64> = ts.values.iter().copied().filter(|&v| v > 0.0).collect();
+    let adi = ts.len() as f64 / sizes.len() as f64;
+    let mean = sizes.iter().sum::<f64>() / sizes.len() as f64;
+    let variance = sizes.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / sizes.len() as f64;
+    let cv2 = variance / (mean * mean);
+
+    let pattern = match (adi > ADI_CUTOFF, cv2 > CV2_CUTOFF) {
+        (false, false) => DemandPattern::Smooth,
+        (false, true) => DemandPattern::Erratic,
+        (true, false) => DemandPattern::Intermittent,
+        (true, true) => DemandPattern::Lumpy,
+    };
+
+    Ok(DemandClassification { adi, cv2, pattern })
+}
+
+/// Demand must be non-negative, complete and contain at least one non-zero value
+fn check_demand(ts: &TimeSeries) -> Result<()> {
+    if ts.is_empty() {
+        return Err(TelemetryError::InsufficientData(
+            "Cannot fit on empty time series".to_string(),
+        ));
+    }
+    if ts.values.iter().any(|v| v.is_nan() || *v < 0.0) {
+        return Err(TelemetryError::InvalidData(
+            "Demand must be non-negative and contain no missing values".to_string(),
+        ));
+    }
+    if ts.values.iter().all(|&v| v == 0.0) {
+        return Err(TelemetryError::InsufficientData(
+            "Demand series has no non-zero values".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_smoothing(value: f64, name: &str) -> Result<()> {
+    if value <= 0.0 || value > 1.0 {
+        return Err(TelemetryError::InvalidParameter(
+            format!("{} must be between 0 and 1", name),
+        ));
+    }
+    Ok(())
+}
+
+/// Empirical interval from in-sample one-step errors, truncated at zero
+fn empirical_interval(base: ForecastResult, errors: &[f64], confidence_level: f64) -> Result<ForecastResult> {
+    z_score(confidence_level)?;
+    if errors.is_empty() {
+        return Err(TelemetryError::InsufficientData(
+            "Need in-sample forecast errors for prediction intervals".to_string(),
+        ));
+    }
+
+    let mut sorted = errors.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let lower_q = quantile_sorted(&sorted, (1.0 - confidence_level) / 2.0);
+    let upper_q = quantile_sorted(&sorted, (1.0 + confidence_level) / 2.0);
+
+    let lower = base.predictions.iter().map(|p| (p + lower_q).max(0.0)).collect();
+    let upper = base.predictions.iter().map(|p| (p + upper_q).max(0.0)).collect();
+    Ok(ForecastResult::with_bounds(base, lower, upper, confidence_level))
+}
+
+fn flat_forecast(value: f64, steps: usize) -> ForecastResult {
+    ForecastResult {
+        predictions: vec![value; steps],
+        lower_bound: None,
+        upper_bound: None,
+        confidence: 0.6,
+        intervals: Vec::new(),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DemandFit {
+    forecast: f64,
+    errors: Vec<f64>,
+}
+
+/// Croston's method, optionally with the Syntetos-Boylan bias correction
+///
+/// Demand sizes `z` and inter-demand intervals `p` are smoothed with the
+/// same `alpha` whenever demand occurs; the forecast per period is `z / p`,
+/// multiplied by `1 - alpha / 2` for SBA.
+#[derive(Debug, Clone)]
+pub struct Croston {
+    alpha: f64,
+    bias_correction: bool,
+    fit: Option<DemandFit>,
+}
+
+impl Croston {
+    /// Classic Croston's method
+    pub fn new(alpha: f64) -> Result<Self> {
+        check_smoothing(alpha, "Alpha")?;
+        Ok(Self {
+            alpha,
+            bias_correction: false,
+            fit: None,
+        })
+    }
+
+    /// Syntetos-Boylan approximation (bias-corrected Croston)
+    pub fn sba(alpha: f64) -> Result<Self> {
+        Ok(Self {
+            bias_correction: true,
+            ..Self::new(alpha)?
+        })
+    }
+
+    fn rate(&self, size: f64, interval: f64) -> f64 {
+        let factor = if self.bias_correction { 1.0 - self.alpha / 2.0 } else { 1.0 };
+        factor * size / interval
+    }
+}
+
+impl Forecaster for Croston {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        check_demand(ts)?;
+
+        // Initialise with the first demand and its position
+        let first = ts.values.iter().position(|&v| v > 0.0).unwrap();
+        let mut size = ts.values[first];
+        let mut interval = (first + 1) as f64;
+        let mut since_demand = 0.0;
+        let mut errors = Vec::with_capacity(ts.len() - first - 1);
+
+        for &value in &ts.values[first + 1..] {
+            since_demand += 1.0;
+            errors.push(value - self.rate(size, interval));
+            if value > 0.0 {
+                size += self.alpha * (value - size);
+                interval += self.alpha * (since_demand - interval);
+                since_demand = 0.0;
+            }
+        }
+
+        self.fit = Some(DemandFit {
+            forecast: self.rate(size, interval),
+            errors,
+        });
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let fit = self.fit.as_ref().ok_or_else(|| {
+            TelemetryError::ModelError("Model must be fitted before forecasting".to_string())
+        })?;
+        Ok(flat_forecast(fit.forecast, steps))
+    }
+
+    /// Empirical intervals from the in-sample one-step errors, truncated at zero
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        empirical_interval(base_forecast, &self.fit.as_ref().unwrap().errors, confidence_level)
+    }
+}
+
+/// Teunter-Syntetos-Babai method
+///
+/// The demand probability is smoothed every period with `beta` and the
+/// demand size with `alpha` when demand occurs; the forecast is their
+/// product, so it decays during long runs of zeros.
+#[derive(Debug, Clone)]
+pub struct TSB {
+    alpha: f64,
+    beta: f64,
+    fit: Option<DemandFit>,
+}
+
+impl TSB {
+    /// Create a TSB forecaster with size and probability smoothing parameters
+    pub fn new(alpha: f64, beta: f64) -> Result<Self> {
+        check_smoothing(alpha, "Alpha")?;
+        check_smoothing(beta, "Beta")?;
+        Ok(Self { alpha, beta, fit: None })
+    }
+}
+
+impl Forecaster for TSB {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        check_demand(ts)?;
+
+        // Initialise with the overall demand probability and mean size
+        let sizes: Vec<f64> = ts.values.iter().copied().filter(|&v| v > 0.0).collect();
+        let mut probability = sizes.len() as f64 / ts.len() as f64;
+        let mut size = sizes.iter().sum::<f64>() / sizes.len() as f64;
+        let mut errors = Vec::with_capacity(ts.len());
+
+        for &value in &ts.values {
+            errors.push(value - probability * size);
+            let occurred = if value > 0.0 { 1.0 } else { 0.0 };
+            probability += self.beta * (occurred - probability);
+            if value > 0.0 {
+                size += self.alpha * (value - size);
+            }
+        }
+
+        self.fit = Some(DemandFit {
+            forecast: probability * size,
+            errors,
+        });
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let fit = self.fit.as_ref().ok_or_else(|| {
+            TelemetryError::ModelError("Model must be fitted before forecasting".to_string())
+        })?;
+        Ok(flat_forecast(fit.forecast, steps))
+    }
+
+    /// Empirical intervals from the in-sample one-step errors, truncated at zero
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        empirical_interval(base_forecast, &self.fit.as_ref().unwrap().errors, confidence_level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_croston_and_sba() {
+        // Demand of 6 every third period
+        let data: Vec<f64> = (0..30).map(|t| if t % 3 == 2 { 6.0 } else { 0.0 }).collect();
+        let ts = TimeSeries::new(data);
+
+        let mut croston = Croston::new(0.2).unwrap();
+        croston.fit(&ts).unwrap();
+        let forecast = croston.forecast(2).unwrap();
+        assert!((forecast.predictions[0] - 2.0).abs() < 1e-12);
+
+        let mut sba = Croston::sba(0.2).unwrap();
+        sba.fit(&ts).unwrap();
+        let forecast = sba.forecast_with_confidence(2, 0.9).unwrap();
+        assert!((forecast.predictions[0] - 1.8).abs() < 1e-12);
+        assert!(forecast.lower_bound.unwrap()[0] >= 0.0);
+    }
+
+    #[test]
+    fn test_tsb_decays_after_obsolescence() {
+        let mut data: Vec<f64> = (0..20).map(|t| if t % 2 == 0 { 4.0 } else { 0.0 }).collect();
+        data.extend(vec![0.0; 10]);
+        let ts = TimeSeries::new(data);
+
+        let mut tsb = TSB::new(0.2, 0.2).unwrap();
+        tsb.fit(&ts).unwrap();
+        let mut croston = Croston::new(0.2).unwrap();
+        croston.fit(&ts).unwrap();
+
+        let tsb_forecast = tsb.forecast(1).unwrap().predictions[0];
+        let croston_forecast = croston.forecast(1).unwrap().predictions[0];
+        assert!(tsb_forecast < 0.5);
+        assert!(croston_forecast > 1.5);
+
+        assert!(TSB::new(0.2, 0.0).is_err());
+        assert!(tsb.fit(&TimeSeries::new(vec![0.0, -1.0, 2.0])).is_err());
+    }
+
+    #[test]
+    fn test_classify_demand() {
+        let smooth = TimeSeries::new(vec![5.0, 6.0, 5.0, 4.0, 6.0, 5.0]);
+        assert_eq!(classify_demand(&smooth).unwrap().pattern, DemandPattern::Smooth);
+
+        let erratic = TimeSeries::new(vec![1.0, 20.0, 2.0, 15.0, 1.0, 30.0]);
+        assert_eq!(classify_demand(&erratic).unwrap().pattern, DemandPattern::Erratic);
+
+        let intermittent = TimeSeries::new(vec![0.0, 5.0, 0.0, 0.0, 6.0, 0.0, 0.0, 5.0]);
+        let class = classify_demand(&intermittent).unwrap();
+        assert_eq!(class.pattern, DemandPattern::Intermittent);
+        assert!((class.adi - 8.0 / 3.0).abs() < 1e-12);
+        assert!(class.pattern.is_intermittent());
+
+        let lumpy = TimeSeries::new(vec![0.0, 1.0, 0.0, 0.0, 25.0, 0.0, 0.0, 2.0]);
+        assert_eq!(classify_demand(&lumpy).unwrap().pattern, DemandPattern::Lumpy);
+
+        assert!(classify_demand(&TimeSeries::new(vec![0.0, 0.0])).is_err());
+    }
+}
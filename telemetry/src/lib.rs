@@ -23,16 +23,54 @@
 //! ```
 
 pub mod time_series;
+pub mod multi_series;
+pub mod align;
 pub mod anomaly;
 pub mod forecasting;
 pub mod features;
 pub mod decomposition;
 pub mod models;
+pub mod resample;
+pub mod rolling;
+pub mod timestamps;
+pub mod missing;
+pub mod ets;
+pub mod backtest;
+pub mod metrics;
+pub mod theta;
+pub mod intermittent;
+pub mod correlation;
+pub mod hypothesis;
+#[cfg(feature = "fft")]
+pub mod spectral;
+
+mod linalg;
+mod optim;
+#[cfg(test)]
+mod test_util;
 
 pub use time_series::TimeSeries;
+pub use multi_series::MultiTimeSeries;
+pub use align::{join, join_asof, JoinType};
 pub use anomaly::{AnomalyDetector, AnomalyType};
-pub use forecasting::{Forecaster, ForecastResult};
+pub use forecasting::{
+    Forecaster, ForecastResult, ExogenousForecaster, PredictionInterval, ExponentialSmoothing,
+    MovingAverageForecaster, Holt, HoltWinters, Seasonality, Mean, Naive, SeasonalNaive, Drift,
+};
 pub use features::FeatureExtractor;
+pub use decomposition::{Decomposer, DecompositionType, DecompositionResult, MSTLForecaster, MSTL, STL};
+pub use resample::{Aggregation, Frequency};
+pub use rolling::{RollingAccumulator, TimeWindow, WindowClosure};
+pub use timestamps::{Gap, TimestampPolicy};
+pub use missing::FillMethod;
+pub use ets::{auto_ets, ErrorType, SeasonalType, TrendType, ETS};
+pub use backtest::{Backtest, BacktestResult, WindowType};
+pub use theta::Theta;
+pub use intermittent::{classify_demand, Croston, DemandPattern, TSB};
+pub use correlation::{acf, ccf, pacf, Correlogram};
+pub use hypothesis::{Deterministic, TestResult};
+#[cfg(feature = "fft")]
+pub use spectral::{detect_periods, dominant_period, CandidatePeriod, Periodogram};
 
 /// Common error type for the library
 #[derive(Debug)]
@@ -0,0 +1,108 @@
+//! Small dense linear algebra helpers used for model fitting
+
+use crate::{Result, TelemetryError};
+
+/// Solve `a · x = b` by Gaussian elimination with partial pivoting
+pub(crate) fn solve(a: &[Vec<f64>], b: &[f64]) -> Result<Vec<f64>> {
+    let n = b.len();
+    let mut m: Vec<Vec<f64>> = a.iter().zip(b).map(|(row, &bi)| {
+        let mut row = row.clone();
+        row.push(bi);
+        row
+    }).collect();
+
+    let scale = a.iter().flatten().fold(0.0f64, |acc, v| acc.max(v.abs())).max(1.0);
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| m[i][col].abs().total_cmp(&m[j][col].abs()))
+            .unwrap();
+        if m[pivot][col].abs() <= 1e-12 * scale {
+            return Err(TelemetryError::ModelError(
+                "Singular system of equations".to_string(),
+            ));
+        }
+        m.swap(col, pivot);
+
+        for row in col + 1..n {
+            let factor = m[row][col] / m[col][col];
+            if factor != 0.0 {
+                let (upper, lower) = m.split_at_mut(row);
+                for (target, source) in lower[0][col..].iter_mut().zip(&upper[col][col..]) {
+                    *target -= factor * source;
+                }
+            }
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let tail: f64 = (i + 1..n).map(|k| m[i][k] * x[k]).sum();
+        x[i] = (m[i][n] - tail) / m[i][i];
+    }
+    Ok(x)
+}
+
+/// Ordinary least squares coefficients of `y` on the columns of `x`
+/// (one row per observation), via the normal equations
+pub(crate) fn least_squares(x: &[Vec<f64>], y: &[f64]) -> Result<Vec<f64>> {
+    let k = x.first().map_or(0, |row| row.len());
+    if x.len() < k || k == 0 {
+        return Err(TelemetryError::InsufficientData(
+            "Need at least as many observations as regressors".to_string(),
+        ));
+    }
+
+    let mut xtx = vec![vec![0.0; k]; k];
+    let mut xty = vec![0.0; k];
+    for (row, &yi) in x.iter().zip(y) {
+        for i in 0..k {
+            xty[i] += row[i] * yi;
+            for j in 0..k {
+                xtx[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    solve(&xtx, &xty)
+}
+
+/// Lower-triangular Cholesky factor `L` with `a = L·Lᵀ`
+pub(crate) fn cholesky(a: &[Vec<f64>]) -> Result<Vec<Vec<f64>>> {
+    let n = a.len();
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let dot: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let d = a[i][i] - dot;
+                if d <= 0.0 {
+                    return Err(TelemetryError::ModelError(
+                        "Matrix is not positive definite".to_string(),
+                    ));
+                }
+                l[i][j] = d.sqrt();
+            } else {
+                l[i][j] = (a[i][j] - dot) / l[j][j];
+            }
+        }
+    }
+    Ok(l)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_least_squares_recovers_line() {
+        let x: Vec<Vec<f64>> = (0..10).map(|t| vec![1.0, t as f64]).collect();
+        let y: Vec<f64> = (0..10).map(|t| 2.0 - 0.5 * t as f64).collect();
+        let beta = least_squares(&x, &y).unwrap();
+        assert!((beta[0] - 2.0).abs() < 1e-10);
+        assert!((beta[1] + 0.5).abs() < 1e-10);
+
+        assert!(solve(&[vec![1.0, 2.0], vec![2.0, 4.0]], &[1.0, 2.0]).is_err());
+
+        let l = cholesky(&[vec![4.0, 2.0], vec![2.0, 5.0]]).unwrap();
+        assert_eq!(l, vec![vec![2.0, 0.0], vec![1.0, 2.0]]);
+    }
+}
@@ -0,0 +1,335 @@
+//! Forecast accuracy metrics
+//!
+//! Point metrics (MAE, RMSE, MAPE, sMAPE, MASE), interval metrics (empirical
+//! coverage, Winkler score) and probabilistic scores (pinball loss, CRPS).
+//! Pairs where the actual or forecast value is missing (`NaN`) are skipped.
+
+use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+
+use crate::{Result, TelemetryError};
+use crate::forecasting::{z_score, ForecastResult};
+
+/// Aligned (actual, forecast) pairs with missing values removed
+fn pairs(actual: &[f64], forecast: &[f64]) -> Result<Vec<(f64, f64)>> {
+    if actual.len() != forecast.len() {
+        return Err(TelemetryError::InvalidData(
+            "Actual and forecast values must have the same length".to_string(),
+        ));
+    }
+
+    let pairs: Vec<(f64, f64)> = actual
+        .iter()
+        .zip(forecast)
+        .filter(|(a, f)| !a.is_nan() && !f.is_nan())
+        .map(|(&a, &f)| (a, f))
+        .collect();
+
+    if pairs.is_empty() {
+        return Err(TelemetryError::InsufficientData(
+            "No non-missing forecast/actual pairs".to_string(),
+        ));
+    }
+    Ok(pairs)
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, n) = values.fold((0.0, 0usize), |(s, n), v| (s + v, n + 1));
+    (n > 0).then(|| sum / n as f64)
+}
+
+/// Mean absolute error
+pub fn mae(actual: &[f64], forecast: &[f64]) -> Result<f64> {
+    let pairs = pairs(actual, forecast)?;
+    Ok(mean(pairs.iter().map(|(a, f)| (a - f).abs())).unwrap())
+}
+
+/// Root mean squared error
+pub fn rmse(actual: &[f64], forecast: &[f64]) -> Result<f64> {
+    let pairs = pairs(actual, forecast)?;
+    Ok(mean(pairs.iter().map(|(a, f)| (a - f).powi(2))).unwrap().sqrt())
+}
+
+/// Mean absolute percentage error, in percent; zero actuals are skipped
+pub fn mape(actual: &[f64], forecast: &[f64]) -> Result<f64> {
+    let pairs = pairs(actual, forecast)?;
+    mean(pairs.iter().filter(|(a, _)| *a != 0.0).map(|(a, f)| ((a - f) / a).abs()))
+        .map(|m| 100.0 * m)
+        .ok_or_else(|| TelemetryError::InvalidData("MAPE is undefined when all actuals are zero".to_string()))
+}
+
+/// Symmetric MAPE `200 |a - f| / (|a| + |f|)`, in percent (0 to 200)
+pub fn smape(actual: &[f64], forecast: &[f64]) -> Result<f64> {
+    let pairs = pairs(actual, forecast)?;
+    Ok(100.0
+        * mean(pairs.iter().map(|(a, f)| {
+            let denom = a.abs() + f.abs();
+            if denom == 0.0 { 0.0 } else { 2.0 * (a - f).abs() / denom }
+        }))
+        .unwrap())
+}
+
+/// Mean absolute scaled error
+///
+/// The MAE is scaled by the in-sample MAE of the seasonal naive forecast
+/// with period `period` on `training` (use 1 for the naive forecast).
+pub fn mase(actual: &[f64], forecast: &[f64], training: &[f64], period: usize) -> Result<f64> {
+    if period == 0 {
+        return Err(TelemetryError::InvalidParameter(
+            "Period must be at least 1".to_string(),
+        ));
+    }
+    if training.len() <= period {
+        return Err(TelemetryError::InsufficientData(
+            "Training data must be longer than the period".to_string(),
+        ));
+    }
+
+    let scale = mean(
+        training
+            .iter()
+            .zip(&training[period..])
+            .map(|(prev, cur)| (cur - prev).abs())
+            .filter(|d| !d.is_nan()),
+    )
+    .unwrap_or(f64::NAN);
+    if scale.is_nan() || scale <= 0.0 {
+        return Err(TelemetryError::InvalidData(
+            "Seasonal naive in-sample error is zero or undefined".to_string(),
+        ));
+    }
+
+    Ok(mae(actual, forecast)? / scale)
+}
+
+/// Fraction of actual values inside `[lower, upper]`
+pub fn coverage(actual: &[f64], lower: &[f64], upper: &[f64]) -> Result<f64> {
+    let bounds = interval_triples(actual, lower, upper)?;
+    Ok(mean(bounds.iter().map(|&(a, l, u)| if a >= l && a <= u { 1.0 } else { 0.0 })).unwrap())
+}
+
+/// Mean Winkler (interval) score of a central interval at `level`
+///
+/// The interval width plus `2 / α` times the distance by which the actual
+/// falls outside it, where `α = 1 - level`. Lower is better.
+pub fn winkler_score(actual: &[f64], lower: &[f64], upper: &[f64], level: f64) -> Result<f64> {
+    z_score(level)?;
+    let alpha = 1.0 - level;
+    let bounds = interval_triples(actual, lower, upper)?;
+    Ok(mean(bounds.iter().map(|&(a, l, u)| {
+        let penalty = if a < l {
+            l - a
+        } else if a > u {
+            a - u
+        } else {
+            0.0
+        };
+        (u - l) + 2.0 / alpha * penalty
+    }))
+    .unwrap())
+}
+
+fn interval_triples(actual: &[f64], lower: &[f64], upper: &[f64]) -> Result<Vec<(f64, f64, f64)>> {
+    if lower.len() != actual.len() || upper.len() != actual.len() {
+        return Err(TelemetryError::InvalidData(
+            "Actual values and interval bounds must have the same length".to_string(),
+        ));
+    }
+
+    let triples: Vec<(f64, f64, f64)> = actual
+        .iter()
+        .zip(lower.iter().zip(upper))
+        .filter(|(a, (l, u))| !a.is_nan() && !l.is_nan() && !u.is_nan())
+        .map(|(&a, (&l, &u))| (a, l, u))
+        .collect();
+
+    if triples.is_empty() {
+        return Err(TelemetryError::InsufficientData(
+            "No non-missing actual/interval triples".to_string(),
+        ));
+    }
+    Ok(triples)
+}
+
+/// Mean pinball (quantile) loss of forecasts of the `tau` quantile
+pub fn pinball_loss(actual: &[f64], quantile_forecast: &[f64], tau: f64) -> Result<f64> {
+    if tau <= 0.0 || tau >= 1.0 {
+        return Err(TelemetryError::InvalidParameter(
+            "Quantile level must be between 0 and 1".to_string(),
+        ));
+    }
+
+    let pairs = pairs(actual, quantile_forecast)?;
+    Ok(mean(pairs.iter().map(|(a, q)| {
+        let diff = a - q;
+        if diff >= 0.0 { tau * diff } else { (tau - 1.0) * diff }
+    }))
+    .unwrap())
+}
+
+/// Mean continuous ranked probability score of Gaussian predictive
+/// distributions `N(mean, std²)`
+pub fn crps_gaussian(actual: &[f64], mean_forecast: &[f64], std: &[f64]) -> Result<f64> {
+    if std.len() != actual.len() {
+        return Err(TelemetryError::InvalidData(
+            "Standard deviations must match the actual values in length".to_string(),
+        ));
+    }
+    if std.iter().any(|&s| s <= 0.0) {
+        return Err(TelemetryError::InvalidParameter(
+            "Standard deviations must be positive".to_string(),
+        ));
+    }
+
+    pairs(actual, mean_forecast)?;
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let scores = actual
+        .iter()
+        .zip(mean_forecast.iter().zip(std))
+        .filter(|(a, (m, s))| !a.is_nan() && !m.is_nan() && !s.is_nan())
+        .map(|(a, (m, s))| {
+            let z = (a - m) / s;
+            s * (z * (2.0 * normal.cdf(z) - 1.0) + 2.0 * normal.pdf(z) - 1.0 / std::f64::consts::PI.sqrt())
+        });
+
+    mean(scores).ok_or_else(|| TelemetryError::InsufficientData("No non-missing values".to_string()))
+}
+
+/// Scores of one prediction interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalScore {
+    /// Nominal coverage of the interval
+    pub level: f64,
+    /// Empirical coverage
+    pub coverage: f64,
+    /// Mean Winkler score
+    pub winkler: f64,
+    /// Mean pinball loss of the two bounds as `(1 ∓ level) / 2` quantiles
+    pub pinball: f64,
+}
+
+/// Accuracy of a forecast against realised values
+#[derive(Debug, Clone)]
+pub struct AccuracyReport {
+    pub mae: f64,
+    pub rmse: f64,
+    /// `None` when all actuals are zero
+    pub mape: Option<f64>,
+    pub smape: f64,
+    /// `None` when the training data cannot provide a seasonal naive scale
+    pub mase: Option<f64>,
+    /// One entry per interval in the forecast
+    pub intervals: Vec<IntervalScore>,
+    /// CRPS assuming a Gaussian predictive distribution whose standard
+    /// deviation is implied by the first interval; `None` without intervals
+    pub crps: Option<f64>,
+}
+
+/// Evaluate a forecast against the realised `actual` values
+///
+/// `training` is the data the model was fitted on and `period` the season
+/// length used to scale MASE (1 for non-seasonal data).
+pub fn evaluate(actual: &[f64], forecast: &ForecastResult, training: &[f64], period: usize) -> Result<AccuracyReport> {
+    let predictions = &forecast.predictions;
+    let point = predictions.get(..actual.len()).ok_or_else(|| {
+        TelemetryError::InvalidData("Forecast is shorter than the actual values".to_string())
+    })?;
+
+    let mut intervals = Vec::with_capacity(forecast.intervals.len());
+    for pi in &forecast.intervals {
+        let n = actual.len();
+        let (lower, upper) = (&pi.lower[..n], &pi.upper[..n]);
+        let tau = (1.0 - pi.level) / 2.0;
+        intervals.push(IntervalScore {
+            level: pi.level,
+            coverage: coverage(actual, lower, upper)?,
+            winkler: winkler_score(actual, lower, upper, pi.level)?,
+            pinball: (pinball_loss(actual, lower, tau)? + pinball_loss(actual, upper, 1.0 - tau)?) / 2.0,
+        });
+    }
+
+    let crps = match forecast.intervals.first() {
+        Some(pi) => {
+            let z = z_score(pi.level)?;
+            let std: Vec<f64> = pi.lower[..actual.len()]
+                .iter()
+                .zip(&pi.upper[..actual.len()])
+                .map(|(l, u)| ((u - l) / (2.0 * z)).max(f64::MIN_POSITIVE))
+                .collect();
+            Some(crps_gaussian(actual, point, &std)?)
+        }
+        None => None,
+    };
+
+    Ok(AccuracyReport {
+        mae: mae(actual, point)?,
+        rmse: rmse(actual, point)?,
+        mape: mape(actual, point).ok(),
+        smape: smape(actual, point)?,
+        mase: mase(actual, point, training, period).ok(),
+        intervals,
+        crps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_metrics() {
+        let actual = [1.0, 2.0, f64::NAN, 4.0];
+        let forecast = [2.0, 2.0, 3.0, 2.0];
+
+        assert!((mae(&actual, &forecast).unwrap() - 1.0).abs() < 1e-12);
+        assert!((rmse(&actual, &forecast).unwrap() - (5.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert!((mape(&actual, &forecast).unwrap() - 50.0).abs() < 1e-12);
+        let expected_smape = 100.0 * (2.0 / 3.0 + 0.0 + 2.0 * 2.0 / 6.0) / 3.0;
+        assert!((smape(&actual, &forecast).unwrap() - expected_smape).abs() < 1e-12);
+
+        // Seasonal naive scale with period 2: |3-1|, |4-2|, |5-3| = 2
+        let training = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!((mase(&actual, &forecast, &training, 2).unwrap() - 0.5).abs() < 1e-12);
+
+        assert!(mae(&[1.0], &[1.0, 2.0]).is_err());
+        assert!(mape(&[0.0], &[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_interval_and_probabilistic_scores() {
+        let actual = [0.0, 3.0, -2.0, 0.5];
+        let lower = [-1.0, -1.0, -1.0, -1.0];
+        let upper = [1.0, 1.0, 1.0, 1.0];
+
+        assert!((coverage(&actual, &lower, &upper).unwrap() - 0.5).abs() < 1e-12);
+        // alpha = 0.2: widths 2, penalties 10 * (2 + 1) / 4 on average
+        let winkler = winkler_score(&actual, &lower, &upper, 0.8).unwrap();
+        assert!((winkler - (2.0 + 10.0 * 3.0 / 4.0)).abs() < 1e-12);
+
+        let median = pinball_loss(&[1.0, -1.0], &[0.0, 0.0], 0.5).unwrap();
+        assert!((median - 0.5).abs() < 1e-12);
+        assert!(pinball_loss(&[1.0], &[0.0], 1.0).is_err());
+
+        // CRPS of N(0, 1) at its mean is (sqrt(2) - 1) / sqrt(pi)
+        let crps = crps_gaussian(&[0.0], &[0.0], &[1.0]).unwrap();
+        let expected = (2.0f64.sqrt() - 1.0) / std::f64::consts::PI.sqrt();
+        assert!((crps - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_evaluate_forecast_result() {
+        use crate::forecasting::{ExponentialSmoothing, Forecaster};
+        use crate::TimeSeries;
+
+        let data: Vec<f64> = (0..60).map(|i| 10.0 + (i as f64 * 0.9).sin()).collect();
+        let (train, test) = data.split_at(50);
+        let mut model = ExponentialSmoothing::new(0.3).unwrap();
+        model.fit(&TimeSeries::new(train.to_vec())).unwrap();
+        let forecast = model.forecast_with_levels(10, &[0.8, 0.95]).unwrap();
+
+        let report = evaluate(test, &forecast, train, 1).unwrap();
+        assert_eq!(report.intervals.len(), 2);
+        assert!(report.intervals[1].coverage >= report.intervals[0].coverage);
+        assert!(report.mase.unwrap() > 0.0);
+        assert!(report.crps.unwrap() > 0.0);
+    }
+}
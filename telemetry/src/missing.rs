@@ -0,0 +1,340 @@
+//! Missing-value handling and interpolation
+//!
+//! Missing observations are represented as `NaN` in `TimeSeries::values`.
+
+use crate::{Result, TelemetryError, TimeSeries};
+
+/// Strategy used to fill missing values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FillMethod {
+    /// Propagate the last observed value forward
+    Forward,
+    /// Propagate the next observed value backward
+    Backward,
+    /// Linear interpolation by position, assuming regular spacing
+    Linear,
+    /// Linear interpolation weighted by the timestamps
+    Time,
+    /// Natural cubic spline through the observed values
+    CubicSpline,
+    /// Interpolate between the same phase of neighbouring cycles
+    Seasonal(usize),
+    /// Replace with a constant
+    Constant(f64),
+}
+
+impl TimeSeries {
+    /// Whether the value at `index` is missing
+    pub fn is_missing(&self, index: usize) -> bool {
+        self.values[index].is_nan()
+    }
+
+    /// Number of missing values
+    pub fn missing_count(&self) -> usize {
+        self.values.iter().filter(|v| v.is_nan()).count()
+    }
+
+    /// Whether the series contains any missing value
+    pub fn has_missing(&self) -> bool {
+        self.values.iter().any(|v| v.is_nan())
+    }
+
+    /// Remove missing observations (and their timestamps)
+    pub fn drop_missing(&self) -> TimeSeries {
+        let keep: Vec<usize> = (0..self.len()).filter(|&i| !self.is_missing(i)).collect();
+
+        TimeSeries {
+            values: keep.iter().map(|&i| self.values[i]).collect(),
+            timestamps: self
+                .timestamps
+                .as_ref()
+                .map(|ts| keep.iter().map(|&i| ts[i]).collect()),
+            name: self.name.clone(),
+        }
+    }
+
+    /// Fill missing values.
+    ///
+    /// With `max_gap`, runs of more than `max_gap` consecutive missing values
+    /// are left untouched. Interpolating methods never extrapolate, so leading
+    /// and trailing gaps are only filled by `Forward`, `Backward` (one side
+    /// each) and `Constant`. `Time`, and `CubicSpline` on a timestamped
+    /// series, require strictly increasing timestamps.
+    pub fn fill_missing(&self, method: FillMethod, max_gap: Option<usize>) -> Result<TimeSeries> {
+        let mut values = match method {
+            FillMethod::Forward => fill_forward(&self.values),
+            FillMethod::Backward => {
+                let mut reversed = self.values.clone();
+                reversed.reverse();
+                let mut filled = fill_forward(&reversed);
+                filled.reverse();
+                filled
+            }
+            FillMethod::Linear => {
+                let x: Vec<f64> = (0..self.len()).map(|i| i as f64).collect();
+                interpolate_linear(&x, &self.values)
+            }
+            FillMethod::Time => {
+                let x = self.time_axis()?;
+                interpolate_linear(&x, &self.values)
+            }
+            FillMethod::CubicSpline => {
+                let x = match self.timestamps {
+                    Some(_) => self.time_axis()?,
+                    None => (0..self.len()).map(|i| i as f64).collect(),
+                };
+                interpolate_spline(&x, &self.values)
+            }
+            FillMethod::Seasonal(period) => {
+                if period == 0 {
+                    return Err(TelemetryError::InvalidParameter(
+                        "Period must be greater than 0".to_string(),
+                    ));
+                }
+                interpolate_seasonal(&self.values, period)
+            }
+            FillMethod::Constant(c) => self
+                .values
+                .iter()
+                .map(|&v| if v.is_nan() { c } else { v })
+                .collect(),
+        };
+
+        if let Some(limit) = max_gap {
+            for (start, end) in missing_runs(&self.values) {
+                if end - start > limit {
+                    values[start..end].iter_mut().for_each(|v| *v = f64::NAN);
+                }
+            }
+        }
+
+        Ok(TimeSeries {
+            values,
+            timestamps: self.timestamps.clone(),
+            name: self.name.clone(),
+        })
+    }
+
+    /// Timestamps as seconds since the first observation; they must be
+    /// strictly increasing
+    fn time_axis(&self) -> Result<Vec<f64>> {
+        let timestamps = self.timestamps.as_ref().ok_or_else(|| {
+            TelemetryError::InvalidData("Time interpolation requires timestamps".to_string())
+        })?;
+        self.validate_timestamps()?;
+
+        let origin = match timestamps.first() {
+            Some(t) => *t,
+            None => return Ok(Vec::new()),
+        };
+
+        Ok(timestamps
+            .iter()
+            .map(|t| (*t - origin).num_milliseconds() as f64 / 1000.0)
+            .collect())
+    }
+}
+
+/// Half-open `[start, end)` ranges of consecutive missing values
+fn missing_runs(values: &[f64]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+
+    for (i, v) in values.iter().enumerate() {
+        match (v.is_nan(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                runs.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(s) = start {
+        runs.push((s, values.len()));
+    }
+
+    runs
+}
+
+fn fill_forward(values: &[f64]) -> Vec<f64> {
+    let mut last = f64::NAN;
+    values
+        .iter()
+        .map(|&v| {
+            if !v.is_nan() {
+                last = v;
+            }
+            last
+        })
+        .collect()
+}
+
+fn interpolate_linear(x: &[f64], values: &[f64]) -> Vec<f64> {
+    let mut result = values.to_vec();
+
+    for (start, end) in missing_runs(values) {
+        if start == 0 || end == values.len() {
+            continue;
+        }
+
+        let (x0, y0) = (x[start - 1], values[start - 1]);
+        let (x1, y1) = (x[end], values[end]);
+        for i in start..end {
+            let w = if x1 == x0 { 0.0 } else { (x[i] - x0) / (x1 - x0) };
+            result[i] = y0 + w * (y1 - y0);
+        }
+    }
+
+    result
+}
+
+fn interpolate_spline(x: &[f64], values: &[f64]) -> Vec<f64> {
+    let known: Vec<usize> = (0..values.len()).filter(|&i| !values[i].is_nan()).collect();
+    if known.len() < 3 {
+        return interpolate_linear(x, values);
+    }
+
+    let xs: Vec<f64> = known.iter().map(|&i| x[i]).collect();
+    let ys: Vec<f64> = known.iter().map(|&i| values[i]).collect();
+    let m = natural_spline_second_derivatives(&xs, &ys);
+
+    let mut result = values.to_vec();
+    let mut segment = 0;
+    for i in known[0]..=known[known.len() - 1] {
+        if !values[i].is_nan() {
+            continue;
+        }
+
+        while xs[segment + 1] < x[i] {
+            segment += 1;
+        }
+
+        let h = xs[segment + 1] - xs[segment];
+        let a = (xs[segment + 1] - x[i]) / h;
+        let b = (x[i] - xs[segment]) / h;
+        result[i] = a * ys[segment]
+            + b * ys[segment + 1]
+            + ((a.powi(3) - a) * m[segment] + (b.powi(3) - b) * m[segment + 1]) * h * h / 6.0;
+    }
+
+    result
+}
+
+/// Second derivatives of the natural cubic spline through `(xs, ys)`
+fn natural_spline_second_derivatives(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut m = vec![0.0; n];
+    let mut c_prime = vec![0.0; n];
+    let mut d_prime = vec![0.0; n];
+
+    // Thomas algorithm on the interior equations
+    for i in 1..n - 1 {
+        let h0 = xs[i] - xs[i - 1];
+        let h1 = xs[i + 1] - xs[i];
+        let diag = 2.0 * (h0 + h1) - h0 * c_prime[i - 1];
+        let rhs = 6.0 * ((ys[i + 1] - ys[i]) / h1 - (ys[i] - ys[i - 1]) / h0);
+        c_prime[i] = h1 / diag;
+        d_prime[i] = (rhs - h0 * d_prime[i - 1]) / diag;
+    }
+
+    for i in (1..n - 1).rev() {
+        m[i] = d_prime[i] - c_prime[i] * m[i + 1];
+    }
+
+    m
+}
+
+fn interpolate_seasonal(values: &[f64], period: usize) -> Vec<f64> {
+    let mut result = values.to_vec();
+
+    for i in 0..values.len() {
+        if !values[i].is_nan() {
+            continue;
+        }
+
+        let before = (1..=i / period)
+            .map(|k| (k, values[i - k * period]))
+            .find(|(_, v)| !v.is_nan());
+        let after = (1..)
+            .map(|k| (k, i + k * period))
+            .take_while(|&(_, j)| j < values.len())
+            .map(|(k, j)| (k, values[j]))
+            .find(|(_, v)| !v.is_nan());
+
+        result[i] = match (before, after) {
+            (Some((kb, vb)), Some((ka, va))) => {
+                vb + (va - vb) * kb as f64 / (kb + ka) as f64
+            }
+            (Some((_, v)), None) | (None, Some((_, v))) => v,
+            (None, None) => f64::NAN,
+        };
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    const NAN: f64 = f64::NAN;
+
+    #[test]
+    fn test_forward_backward_and_linear() {
+        let ts = TimeSeries::new(vec![NAN, 1.0, NAN, NAN, 4.0, NAN]);
+        assert_eq!(ts.missing_count(), 4);
+
+        let ffill = ts.fill_missing(FillMethod::Forward, None).unwrap();
+        assert!(ffill.values[0].is_nan());
+        assert_eq!(&ffill.values[1..], &[1.0, 1.0, 1.0, 4.0, 4.0]);
+
+        let bfill = ts.fill_missing(FillMethod::Backward, None).unwrap();
+        assert_eq!(&bfill.values[..5], &[1.0, 1.0, 4.0, 4.0, 4.0]);
+        assert!(bfill.values[5].is_nan());
+
+        let linear = ts.fill_missing(FillMethod::Linear, None).unwrap();
+        assert_eq!(&linear.values[1..5], &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(linear.drop_missing().len(), 4);
+    }
+
+    #[test]
+    fn test_time_weighted_and_max_gap() {
+        let t0 = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let timestamps = vec![t0, t0 + Duration::hours(3), t0 + Duration::hours(4)];
+        let ts = TimeSeries::with_timestamps(vec![0.0, NAN, 8.0], timestamps).unwrap();
+
+        let filled = ts.fill_missing(FillMethod::Time, None).unwrap();
+        assert_eq!(filled.values[1], 6.0);
+
+        // Unsorted or duplicate timestamps are rejected rather than
+        // interpolated against a non-monotone axis
+        let minutes = |m: &[i64]| m.iter().map(|&m| t0 + Duration::minutes(m)).collect::<Vec<_>>();
+        let unsorted = TimeSeries::with_timestamps(vec![1.0, NAN, 2.0, 3.0], minutes(&[0, 100, 10, 20])).unwrap();
+        assert!(unsorted.fill_missing(FillMethod::Time, None).is_err());
+        assert!(unsorted.fill_missing(FillMethod::CubicSpline, None).is_err());
+        let duplicate = TimeSeries::with_timestamps(vec![1.0, NAN, 2.0, 3.0], minutes(&[0, 10, 10, 20])).unwrap();
+        assert!(duplicate.fill_missing(FillMethod::Time, None).is_err());
+
+        let gappy = TimeSeries::new(vec![1.0, NAN, 3.0, NAN, NAN, NAN, 7.0]);
+        let limited = gappy.fill_missing(FillMethod::Linear, Some(2)).unwrap();
+        assert_eq!(limited.values[1], 2.0);
+        assert!(limited.values[3..6].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_spline_and_seasonal() {
+        let values: Vec<f64> = (0..8)
+            .map(|i| if i == 4 { NAN } else { (i * i) as f64 })
+            .collect();
+        let ts = TimeSeries::new(values);
+        let spline = ts.fill_missing(FillMethod::CubicSpline, None).unwrap();
+        assert!((spline.values[4] - 16.0).abs() < 0.5);
+
+        let seasonal = TimeSeries::new(vec![1.0, 5.0, 2.0, 6.0, NAN, 7.0]);
+        let filled = seasonal.fill_missing(FillMethod::Seasonal(2), None).unwrap();
+        assert_eq!(filled.values[4], 2.0);
+    }
+}
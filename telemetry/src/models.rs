@@ -1,6 +1,14 @@
 //! Statistical models for time series
 
 pub mod arima;
+pub mod sarima;
+pub mod auto_arima;
+pub mod arimax;
+pub mod var;
 
 // Re-export commonly used types
 pub use arima::ARIMA;
+pub use sarima::SARIMA;
+pub use auto_arima::{auto_arima, AutoARIMA};
+pub use arimax::ARIMAX;
+pub use var::{auto_var, InformationCriterion, VAR};
@@ -1,7 +1,20 @@
 //! ARIMA (AutoRegressive Integrated Moving Average) model
+//!
+//! Pure AR models are estimated with Yule-Walker (Levinson-Durbin); models
+//! with MA terms are fitted by conditional sum of squares (CSS), which is the
+//! conditional Gaussian maximum likelihood. Coefficients are searched in a
+//! transformed space that keeps the AR part stationary and the MA part
+//! invertible. The reported log-likelihood is the exact one, evaluated by a
+//! Kalman filter at the estimates: the conditional likelihood depends on the
+//! AR order through its conditioning sample and can be inflated by MA roots
+//! near the unit circle, so it cannot rank models of different orders.
 
 use crate::{Result, TelemetryError, TimeSeries};
 use crate::forecasting::{Forecaster, ForecastResult};
+use crate::optim::nelder_mead;
+
+/// Maximum number of ψ-weight terms summed for the stationary state covariance
+const MAX_COVARIANCE_TERMS: usize = 10_000;
 
 /// ARIMA model parameters
 #[derive(Debug, Clone)]
@@ -15,98 +28,132 @@ pub struct ARIMAParams {
 }
 
 /// ARIMA model
+///
+/// Uses the convention
+/// `(1 - φ₁B - … - φₚBᵖ)(wₜ - μ) = (1 + θ₁B + … + θ_qB^q)εₜ`
+/// where `wₜ` is the d-times differenced series and `μ` the optional constant
+/// (a mean when `d = 0`, a drift when `d = 1`).
+#[derive(Debug, Clone)]
 pub struct ARIMA {
     params: ARIMAParams,
+    include_constant: bool,
     ar_coeffs: Vec<f64>,
     ma_coeffs: Vec<f64>,
+    constant: f64,
+    sigma2: f64,
+    log_likelihood: f64,
+    n_used: usize,
     fitted: bool,
+    history: Vec<f64>,
     differenced_data: Vec<f64>,
+    residuals: Vec<f64>,
 }
 
 impl ARIMA {
     /// Create a new ARIMA model
+    ///
+    /// A constant is included by default only when `d = 0`.
     pub fn new(p: usize, d: usize, q: usize) -> Self {
         Self {
             params: ARIMAParams { p, d, q },
+            include_constant: d == 0,
             ar_coeffs: vec![0.0; p],
             ma_coeffs: vec![0.0; q],
+            constant: 0.0,
+            sigma2: 0.0,
+            log_likelihood: 0.0,
+            n_used: 0,
             fitted: false,
+            history: Vec::new(),
             differenced_data: Vec::new(),
+            residuals: Vec::new(),
         }
     }
 
-    /// Difference the time series d times
-    fn difference(&self, data: &[f64]) -> Vec<f64> {
-        let mut result = data.to_vec();
+    /// Include (or exclude) the constant term: a mean for `d = 0`, a drift for `d = 1`
+    pub fn with_constant(mut self, include: bool) -> Self {
+        self.include_constant = include;
+        self
+    }
 
-        for _ in 0..self.params.d {
-            result = result.windows(2)
-                .map(|w| w[1] - w[0])
-                .collect();
-        }
+    /// Model orders
+    pub fn params(&self) -> &ARIMAParams {
+        &self.params
+    }
 
-        result
+    /// Fitted AR coefficients φ₁..φₚ
+    pub fn ar_coeffs(&self) -> &[f64] {
+        &self.ar_coeffs
     }
 
-    /// Integrate (cumulative sum) the differenced data
-    fn integrate(&self, differenced: &[f64], original_data: &[f64]) -> Vec<f64> {
-        let mut result = differenced.to_vec();
+    /// Fitted MA coefficients θ₁..θ_q
+    pub fn ma_coeffs(&self) -> &[f64] {
+        &self.ma_coeffs
+    }
 
-        for _ in 0..self.params.d {
-            let mut integrated = Vec::with_capacity(result.len() + 1);
-            let start_value = if self.params.d == 1 {
-                *original_data.last().unwrap()
-            } else {
-                0.0
-            };
-            integrated.push(start_value);
+    /// Fitted mean (d = 0) or drift (d = 1); zero when no constant is included
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
 
-            for &val in &result {
-                integrated.push(integrated.last().unwrap() + val);
-            }
+    /// Innovation variance σ², the Yule-Walker or conditional-sum-of-squares
+    /// estimate
+    ///
+    /// Forecast intervals use this value; the exact likelihood behind
+    /// [`log_likelihood`](Self::log_likelihood) concentrates its own variance.
+    pub fn sigma2(&self) -> f64 {
+        self.sigma2
+    }
 
-            result = integrated;
-        }
+    /// In-sample one-step residuals on the differenced scale
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
 
-        result
+    /// Exact Gaussian log-likelihood of the differenced series at the
+    /// fitted coefficients
+    ///
+    /// The coefficients are the CSS (or Yule-Walker) estimates, not the
+    /// exact maximum-likelihood ones, so this value and the information
+    /// criteria can sit slightly below those of a full MLE fit. Every
+    /// differenced observation contributes, so they compare across ARMA
+    /// orders with the same differencing.
+    pub fn log_likelihood(&self) -> Option<f64> {
+        self.fitted.then_some(self.log_likelihood)
     }
 
-    /// Simple AR coefficient estimation using Yule-Walker equations
-    fn estimate_ar_coeffs(&mut self, data: &[f64]) -> Result<()> {
-        if data.len() <= self.params.p {
-            return Err(TelemetryError::InsufficientData(
-                "Not enough data for AR estimation".to_string(),
-            ));
-        }
+    /// Akaike information criterion
+    pub fn aic(&self) -> Option<f64> {
+        self.log_likelihood().map(|ll| aic(ll, self.n_params()))
+    }
 
-        // Simplified estimation: use least squares approximation
-        for i in 0..self.params.p {
-            let lag = i + 1;
-            let mut sum_xy = 0.0;
-            let mut sum_x2 = 0.0;
+    /// Small-sample corrected AIC
+    pub fn aicc(&self) -> Option<f64> {
+        self.log_likelihood().map(|ll| aicc(ll, self.n_params(), self.n_used))
+    }
 
-            for j in lag..data.len() {
-                sum_xy += data[j] * data[j - lag];
-                sum_x2 += data[j - lag] * data[j - lag];
-            }
+    /// Bayesian information criterion
+    pub fn bic(&self) -> Option<f64> {
+        self.log_likelihood().map(|ll| bic(ll, self.n_params(), self.n_used))
+    }
 
-            self.ar_coeffs[i] = if sum_x2 != 0.0 {
-                sum_xy / sum_x2
-            } else {
-                0.0
-            };
-        }
+    /// Number of estimated parameters, including σ²
+    fn n_params(&self) -> usize {
+        self.params.p + self.params.q + usize::from(self.include_constant) + 1
+    }
 
-        Ok(())
+    /// Difference the time series d times
+    fn difference(&self, data: &[f64]) -> Vec<f64> {
+        apply_difference(data, &difference_polynomial(self.params.d, 0, 0))
     }
 
-    /// Simple MA coefficient estimation
-    fn estimate_ma_coeffs(&mut self, _residuals: &[f64]) -> Result<()> {
-        // Simplified: initialize with small random values
-        for i in 0..self.params.q {
-            self.ma_coeffs[i] = 0.1 * (i as f64 + 1.0) / (self.params.q as f64);
-        }
-        Ok(())
+    /// Point forecasts on the original scale
+    fn forecast_values(&self, steps: usize) -> Vec<f64> {
+        let z: Vec<f64> = self.differenced_data.iter().map(|w| w - self.constant).collect();
+        let arma = forecast_arma(&z, &self.residuals, &self.ar_coeffs, &self.ma_coeffs, steps);
+        let w: Vec<f64> = arma.iter().map(|z| z + self.constant).collect();
+
+        integrate(&self.history, &w, &difference_polynomial(self.params.d, 0, 0))
     }
 }
 
@@ -118,18 +165,39 @@ impl Forecaster for ARIMA {
             ));
         }
 
-        // Difference the data
-        self.differenced_data = self.difference(&ts.values);
-
-        // Estimate AR coefficients
-        self.estimate_ar_coeffs(&self.differenced_data)?;
-
-        // Calculate residuals (simplified)
-        let residuals = self.differenced_data.clone();
+        if self.include_constant && self.params.d > 1 {
+            return Err(TelemetryError::InvalidParameter(
+                "A constant is only supported for d <= 1".to_string(),
+            ));
+        }
 
-        // Estimate MA coefficients
-        self.estimate_ma_coeffs(&residuals)?;
+        if ts.has_missing() {
+            return Err(TelemetryError::InvalidData(
+                "ARIMA cannot be fitted on a series with missing values".to_string(),
+            ));
+        }
 
+        let differenced = self.difference(&ts.values);
+        let fit = if self.params.q == 0 {
+            fit_ar(&differenced, self.params.p, self.include_constant)?
+        } else {
+            let spec = ArmaSpec {
+                ar_blocks: vec![self.params.p],
+                ma_blocks: vec![self.params.q],
+                include_constant: self.include_constant,
+            };
+            fit_arma(&differenced, &spec, |ar, ma| (ar.to_vec(), ma.to_vec()))?
+        };
+
+        self.ar_coeffs = fit.ar;
+        self.ma_coeffs = fit.ma;
+        self.constant = fit.constant;
+        self.sigma2 = fit.sigma2;
+        self.log_likelihood = fit.log_likelihood;
+        self.n_used = fit.n_used;
+        self.residuals = fit.residuals;
+        self.history = ts.values.clone();
+        self.differenced_data = differenced;
         self.fitted = true;
         Ok(())
     }
@@ -141,60 +209,494 @@ impl Forecaster for ARIMA {
             ));
         }
 
-        let mut predictions = Vec::with_capacity(steps);
-        let mut history = self.differenced_data.clone();
-
-        for _ in 0..steps {
-            let mut pred = 0.0;
-
-            // AR component
-            for (i, &coeff) in self.ar_coeffs.iter().enumerate() {
-                if i < history.len() {
-                    pred += coeff * history[history.len() - 1 - i];
-                }
-            }
-
-            predictions.push(pred);
-            history.push(pred);
-        }
-
-        // Note: This is simplified - should integrate back
         Ok(ForecastResult {
-            predictions,
+            predictions: self.forecast_values(steps),
             lower_bound: None,
             upper_bound: None,
             confidence: 0.75,
+            intervals: Vec::new(),
         })
     }
 
     fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
         let base_forecast = self.forecast(steps)?;
 
-        // Simple confidence interval calculation
-        let std_dev = 1.0; // Simplified - should calculate from residuals
-        let z_score = if confidence_level >= 0.95 { 1.96 } else { 1.645 };
-        let margin = z_score * std_dev;
+        let diff_poly = difference_polynomial(self.params.d, 0, 0);
+        let psi = psi_weights(&self.ar_coeffs, &self.ma_coeffs, &diff_poly, steps);
+        let std_errors = forecast_std_errors(self.sigma2, &psi);
+
+        ForecastResult::with_normal_interval(base_forecast, &std_errors, confidence_level)
+    }
+}
+
+/// Structure of the ARMA part being estimated
+///
+/// Each block is a separate lag polynomial (e.g. non-seasonal and seasonal)
+/// constrained to be stationary (AR) or invertible (MA) on its own.
+pub(crate) struct ArmaSpec {
+    /// Orders of the AR polynomial blocks
+    pub ar_blocks: Vec<usize>,
+    /// Orders of the MA polynomial blocks
+    pub ma_blocks: Vec<usize>,
+    pub include_constant: bool,
+}
+
+impl ArmaSpec {
+    /// Total number of free AR parameters
+    pub fn p(&self) -> usize {
+        self.ar_blocks.iter().sum()
+    }
+
+    /// Total number of free MA parameters
+    pub fn q(&self) -> usize {
+        self.ma_blocks.iter().sum()
+    }
+}
+
+/// Estimated ARMA parameters
+pub(crate) struct ArmaFit {
+    pub ar: Vec<f64>,
+    pub ma: Vec<f64>,
+    pub constant: f64,
+    pub sigma2: f64,
+    pub log_likelihood: f64,
+    /// Number of observations entering the likelihood
+    pub n_used: usize,
+    pub residuals: Vec<f64>,
+}
+
+/// Fit a pure AR(p) model to an already differenced series by Yule-Walker
+pub(crate) fn fit_ar(w: &[f64], p: usize, include_constant: bool) -> Result<ArmaFit> {
+    let mean = if include_constant {
+        w.iter().sum::<f64>() / w.len() as f64
+    } else {
+        0.0
+    };
+    let centered: Vec<f64> = w.iter().map(|x| x - mean).collect();
+    let ar = yule_walker(&centered, p);
+
+    finish_fit(w, ar, Vec::new(), mean, |ar, ma| (ar.to_vec(), ma.to_vec()))
+}
 
-        let lower_bound = base_forecast.predictions.iter()
-            .map(|&v| v - margin)
+/// Fit an ARMA model to an already differenced series by conditional sum of squares.
+///
+/// `expand` maps the free AR and MA parameters (blocks concatenated) to the
+/// full lag polynomials (identity for plain ARMA, a polynomial product for
+/// seasonal models).
+pub(crate) fn fit_arma<E>(w: &[f64], spec: &ArmaSpec, expand: E) -> Result<ArmaFit>
+where
+    E: Fn(&[f64], &[f64]) -> (Vec<f64>, Vec<f64>),
+{
+    let mean = if spec.include_constant {
+        w.iter().sum::<f64>() / w.len() as f64
+    } else {
+        0.0
+    };
+    let centered: Vec<f64> = w.iter().map(|x| x - mean).collect();
+
+    let (p, q) = (spec.p(), spec.q());
+
+    // Start from Yule-Walker estimates for the first AR block, zero elsewhere
+    let first_block = spec.ar_blocks.first().copied().unwrap_or(0);
+    let mut x0: Vec<f64> = ar_to_pacf(&yule_walker(&centered, first_block))
+        .iter()
+        .map(|r| r.clamp(-0.95, 0.95).atanh())
+        .collect();
+    x0.extend(std::iter::repeat_n(0.0, p - first_block + q));
+
+    // Search on a unit-variance scale so the simplex steps and tolerances,
+    // and hence the estimates, do not depend on the units of the data
+    let scale = (centered.iter().map(|v| v * v).sum::<f64>() / w.len() as f64).sqrt();
+    let scale = if scale > 0.0 { scale } else { 1.0 };
+    if spec.include_constant {
+        x0.push(mean / scale);
+    }
+
+    let unpack = |x: &[f64]| {
+        let ar = transform_blocks(&x[..p], &spec.ar_blocks);
+        let ma: Vec<f64> = transform_blocks(&x[p..p + q], &spec.ma_blocks)
+            .iter()
+            .map(|a| -a)
             .collect();
+        let constant = if spec.include_constant { x[p + q] * scale } else { 0.0 };
+        (ar, ma, constant)
+    };
+    let objective = |x: &[f64]| {
+        let (ar, ma, c) = unpack(x);
+        let (full_ar, full_ma) = expand(&ar, &ma);
+        let z: Vec<f64> = w.iter().map(|v| v - c).collect();
+        let e = css_residuals(&z, &full_ar, &full_ma);
+        e[full_ar.len().min(e.len())..].iter().map(|v| v * v).sum::<f64>() / (scale * scale)
+    };
+
+    let (mut best, mut best_value) = nelder_mead(objective, &x0, 0.1, 2000, 1e-10);
+    // Restart once from the optimum to escape premature simplex collapse
+    let (restart, restart_value) = nelder_mead(objective, &best, 0.05, 2000, 1e-12);
+    if restart_value < best_value {
+        best = restart;
+        best_value = restart_value;
+    }
+    if !best_value.is_finite() {
+        return Err(TelemetryError::ModelError(
+            "ARMA estimation did not converge".to_string(),
+        ));
+    }
+
+    let (ar, ma, constant) = unpack(&best);
+    finish_fit(w, ar, ma, constant, expand)
+}
+
+/// Map unconstrained values to stationary AR coefficients, block by block
+fn transform_blocks(x: &[f64], blocks: &[usize]) -> Vec<f64> {
+    let mut result = Vec::with_capacity(x.len());
+    let mut offset = 0;
+    for &order in blocks {
+        let pacf: Vec<f64> = x[offset..offset + order].iter().map(|u| u.tanh()).collect();
+        result.extend(pacf_to_ar(&pacf));
+        offset += order;
+    }
+    result
+}
+
+/// Residuals, innovation variance and likelihood for estimated parameters
+fn finish_fit<E>(w: &[f64], ar: Vec<f64>, ma: Vec<f64>, constant: f64, expand: E) -> Result<ArmaFit>
+where
+    E: Fn(&[f64], &[f64]) -> (Vec<f64>, Vec<f64>),
+{
+    let (full_ar, full_ma) = expand(&ar, &ma);
+    let z: Vec<f64> = w.iter().map(|v| v - constant).collect();
+    let residuals = css_residuals(&z, &full_ar, &full_ma);
+    let start = full_ar.len().min(residuals.len());
+    let n_used = residuals.len() - start;
+    if n_used == 0 {
+        return Err(TelemetryError::InsufficientData(
+            "No observations left after conditioning on initial values".to_string(),
+        ));
+    }
+
+    let sse: f64 = residuals[start..].iter().map(|e| e * e).sum();
+    let sigma2 = sse / n_used as f64;
+    let log_likelihood = exact_log_likelihood(&z, &full_ar, &full_ma);
+
+    Ok(ArmaFit {
+        ar,
+        ma,
+        constant,
+        sigma2,
+        log_likelihood,
+        n_used: z.len(),
+        residuals,
+    })
+}
+
+/// Akaike information criterion for `k` estimated parameters
+pub(crate) fn aic(log_likelihood: f64, k: usize) -> f64 {
+    -2.0 * log_likelihood + 2.0 * k as f64
+}
+
+/// AIC with the small-sample correction for `n` observations
+pub(crate) fn aicc(log_likelihood: f64, k: usize, n: usize) -> f64 {
+    let (k, n) = (k as f64, n as f64);
+    if n - k - 1.0 > 0.0 {
+        -2.0 * log_likelihood + 2.0 * k + 2.0 * k * (k + 1.0) / (n - k - 1.0)
+    } else {
+        f64::INFINITY
+    }
+}
+
+/// Bayesian information criterion
+pub(crate) fn bic(log_likelihood: f64, k: usize, n: usize) -> f64 {
+    -2.0 * log_likelihood + k as f64 * (n as f64).ln()
+}
+
+/// Concentrated Gaussian log-likelihood for a given innovation variance
+pub(crate) fn gaussian_log_likelihood(sigma2: f64, n: usize) -> f64 {
+    let sigma2 = sigma2.max(f64::MIN_POSITIVE);
+    -0.5 * n as f64 * ((2.0 * std::f64::consts::PI * sigma2).ln() + 1.0)
+}
+
+/// Exact Gaussian log-likelihood of a stationary ARMA model for a zero-mean
+/// series, with the innovation variance concentrated out
+///
+/// Kalman filter on Harvey's state-space form, started from the stationary
+/// state covariance `Σₖ (Tᵏg)(Tᵏg)'`.
+pub(crate) fn exact_log_likelihood(z: &[f64], ar: &[f64], ma: &[f64]) -> f64 {
+    let r = ar.len().max(ma.len() + 1);
+    let phi: Vec<f64> = (0..r).map(|i| ar.get(i).copied().unwrap_or(0.0)).collect();
+    let g: Vec<f64> = (0..r)
+        .map(|i| if i == 0 { 1.0 } else { ma.get(i - 1).copied().unwrap_or(0.0) })
+        .collect();
+    // Companion transition: (Tx)ᵢ = φᵢx₀ + xᵢ₊₁
+    let transition = |x: &[f64]| -> Vec<f64> {
+        (0..r).map(|i| phi[i] * x[0] + x.get(i + 1).copied().unwrap_or(0.0)).collect()
+    };
+
+    let mut p = vec![vec![0.0; r]; r];
+    let mut v = g.clone();
+    for _ in 0..MAX_COVARIANCE_TERMS {
+        for i in 0..r {
+            for j in 0..r {
+                p[i][j] += v[i] * v[j];
+            }
+        }
+        if v.iter().map(|x| x * x).sum::<f64>() < 1e-14 {
+            break;
+        }
+        v = transition(&v);
+    }
 
-        let upper_bound = base_forecast.predictions.iter()
-            .map(|&v| v + margin)
+    let mut a = vec![0.0; r];
+    let (mut sum_squares, mut sum_log_f) = (0.0, 0.0);
+    for &y in z {
+        // Update with the observation; F ≥ 1 because g₀ = 1
+        let f = p[0][0];
+        let innovation = y - a[0];
+        sum_squares += innovation * innovation / f;
+        sum_log_f += f.ln();
+        let gain: Vec<f64> = (0..r).map(|i| p[i][0] / f).collect();
+        let first_row = p[0].clone();
+        for i in 0..r {
+            a[i] += gain[i] * innovation;
+            for j in 0..r {
+                p[i][j] -= gain[i] * first_row[j];
+            }
+        }
+
+        // Predict: a ← Ta, P ← TPT' + gg'
+        a = transition(&a);
+        let tp: Vec<Vec<f64>> = (0..r)
+            .map(|i| (0..r).map(|j| phi[i] * p[0][j] + if i + 1 < r { p[i + 1][j] } else { 0.0 }).collect())
             .collect();
+        for i in 0..r {
+            for j in 0..r {
+                p[i][j] = phi[j] * tp[i][0] + if j + 1 < r { tp[i][j + 1] } else { 0.0 } + g[i] * g[j];
+            }
+        }
+    }
 
-        Ok(ForecastResult {
-            predictions: base_forecast.predictions,
-            lower_bound: Some(lower_bound),
-            upper_bound: Some(upper_bound),
-            confidence: confidence_level,
+    gaussian_log_likelihood(sum_squares / z.len() as f64, z.len()) - 0.5 * sum_log_f
+}
+
+/// Sample autocovariances for lags 0..=max_lag of an already centred series
+pub(crate) fn autocovariance(x: &[f64], max_lag: usize) -> Vec<f64> {
+    let n = x.len();
+    (0..=max_lag)
+        .map(|k| {
+            if k >= n {
+                0.0
+            } else {
+                x[k..].iter().zip(x.iter()).map(|(a, b)| a * b).sum::<f64>() / n as f64
+            }
+        })
+        .collect()
+}
+
+/// Levinson-Durbin recursion.
+///
+/// Returns the AR(p) coefficients and the partial autocorrelations solving
+/// the Yule-Walker equations for the autocovariances `acov[0..=p]`.
+pub(crate) fn levinson_durbin(acov: &[f64], p: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut phi = vec![0.0; p];
+    let mut pacf = vec![0.0; p];
+    if p == 0 || acov[0] <= 0.0 {
+        return (phi, pacf);
+    }
+
+    let mut variance = acov[0];
+    for k in 0..p {
+        let num = acov[k + 1] - (0..k).map(|j| phi[j] * acov[k - j]).sum::<f64>();
+        let reflection = if variance > 0.0 { num / variance } else { 0.0 };
+
+        let previous = phi.clone();
+        phi[k] = reflection;
+        for j in 0..k {
+            phi[j] = previous[j] - reflection * previous[k - 1 - j];
+        }
+
+        pacf[k] = reflection;
+        variance *= 1.0 - reflection * reflection;
+    }
+
+    (phi, pacf)
+}
+
+/// Yule-Walker AR(p) estimates for a centred series
+fn yule_walker(x: &[f64], p: usize) -> Vec<f64> {
+    levinson_durbin(&autocovariance(x, p), p).0
+}
+
+/// Map partial autocorrelations in (-1, 1) to stationary AR coefficients
+pub(crate) fn pacf_to_ar(pacf: &[f64]) -> Vec<f64> {
+    let mut phi: Vec<f64> = Vec::with_capacity(pacf.len());
+    for (k, &r) in pacf.iter().enumerate() {
+        let previous = phi.clone();
+        for j in 0..k {
+            phi[j] = previous[j] - r * previous[k - 1 - j];
+        }
+        phi.push(r);
+    }
+    phi
+}
+
+/// Inverse of [`pacf_to_ar`] (partial autocorrelations of a stationary AR)
+pub(crate) fn ar_to_pacf(ar: &[f64]) -> Vec<f64> {
+    let mut phi = ar.to_vec();
+    let mut pacf = vec![0.0; ar.len()];
+    for k in (0..ar.len()).rev() {
+        let r = phi[k];
+        pacf[k] = r;
+        let denom = 1.0 - r * r;
+        if denom.abs() < 1e-12 {
+            break;
+        }
+        let previous = phi.clone();
+        for j in 0..k {
+            phi[j] = (previous[j] + r * previous[k - 1 - j]) / denom;
+        }
+    }
+    pacf
+}
+
+/// One-step conditional residuals of an ARMA model on a zero-mean series.
+///
+/// The first `ar.len()` residuals are conditioned to zero.
+pub(crate) fn css_residuals(z: &[f64], ar: &[f64], ma: &[f64]) -> Vec<f64> {
+    let mut e = vec![0.0; z.len()];
+    for t in ar.len()..z.len() {
+        let mut pred = 0.0;
+        for (i, &phi) in ar.iter().enumerate() {
+            pred += phi * z[t - 1 - i];
+        }
+        for (j, &theta) in ma.iter().enumerate() {
+            if t > j {
+                pred += theta * e[t - 1 - j];
+            }
+        }
+        e[t] = z[t] - pred;
+    }
+    e
+}
+
+/// Multi-step ARMA forecasts of a zero-mean series with future shocks set to zero
+pub(crate) fn forecast_arma(z: &[f64], residuals: &[f64], ar: &[f64], ma: &[f64], steps: usize) -> Vec<f64> {
+    let n = z.len();
+    let mut extended = z.to_vec();
+    let mut shocks = residuals.to_vec();
+
+    for _ in 0..steps {
+        let t = extended.len();
+        let mut pred = 0.0;
+        for (i, &phi) in ar.iter().enumerate() {
+            if t > i {
+                pred += phi * extended[t - 1 - i];
+            }
+        }
+        for (j, &theta) in ma.iter().enumerate() {
+            if t > j {
+                pred += theta * shocks[t - 1 - j];
+            }
+        }
+        extended.push(pred);
+        shocks.push(0.0);
+    }
+
+    extended[n..].to_vec()
+}
+
+/// ψ-weights ψ₀..ψₙ₋₁ of the MA(∞) representation of an ARIMA process.
+///
+/// `ar` and `ma` are the full lag polynomials (in the `φ₁..` / `θ₁..` sign
+/// convention) and `diff_poly` the differencing polynomial.
+pub(crate) fn psi_weights(ar: &[f64], ma: &[f64], diff_poly: &[f64], n: usize) -> Vec<f64> {
+    let mut ar_poly = vec![1.0];
+    ar_poly.extend(ar.iter().map(|c| -c));
+    let phi: Vec<f64> = multiply_polynomials(&ar_poly, diff_poly)[1..]
+        .iter()
+        .map(|c| -c)
+        .collect();
+
+    let mut psi = Vec::with_capacity(n);
+    for j in 0..n {
+        let value = if j == 0 {
+            1.0
+        } else {
+            let theta = ma.get(j - 1).copied().unwrap_or(0.0);
+            theta + (1..=j.min(phi.len())).map(|i| phi[i - 1] * psi[j - i]).sum::<f64>()
+        };
+        psi.push(value);
+    }
+    psi
+}
+
+/// Standard errors of the 1..=h step forecasts: `σ·sqrt(Σ_{j<h} ψⱼ²)`
+pub(crate) fn forecast_std_errors(sigma2: f64, psi: &[f64]) -> Vec<f64> {
+    let mut cumulative = 0.0;
+    psi.iter()
+        .map(|p| {
+            cumulative += p * p;
+            (sigma2 * cumulative).sqrt()
         })
+        .collect()
+}
+
+/// Coefficients of `(1 - B)^d (1 - B^s)^D`, starting with the lag-0 term
+pub(crate) fn difference_polynomial(d: usize, seasonal_d: usize, period: usize) -> Vec<f64> {
+    let mut poly = vec![1.0];
+    for _ in 0..d {
+        poly = multiply_polynomials(&poly, &[1.0, -1.0]);
+    }
+    for _ in 0..seasonal_d {
+        let mut factor = vec![0.0; period + 1];
+        factor[0] = 1.0;
+        factor[period] = -1.0;
+        poly = multiply_polynomials(&poly, &factor);
+    }
+    poly
+}
+
+/// Apply a differencing polynomial, dropping the first `poly.len() - 1` values
+pub(crate) fn apply_difference(data: &[f64], poly: &[f64]) -> Vec<f64> {
+    let order = poly.len() - 1;
+    if data.len() <= order {
+        return Vec::new();
+    }
+
+    (order..data.len())
+        .map(|t| poly.iter().enumerate().map(|(j, c)| c * data[t - j]).sum())
+        .collect()
+}
+
+/// Undo differencing: turn forecasts of the differenced series into forecasts
+/// of the original series, given its observed history
+pub(crate) fn integrate(history: &[f64], differenced_forecast: &[f64], poly: &[f64]) -> Vec<f64> {
+    let mut extended = history.to_vec();
+    for &w in differenced_forecast {
+        let t = extended.len();
+        let value = w - poly.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(j, c)| c * extended[t - j])
+            .sum::<f64>();
+        extended.push(value);
+    }
+    extended[history.len()..].to_vec()
+}
+
+/// Product of two polynomials given by their coefficients
+pub(crate) fn multiply_polynomials(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
     }
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::shocks;
 
     #[test]
     fn test_arima_creation() {
@@ -215,4 +717,92 @@ mod tests {
         let forecast = model.forecast(5).unwrap();
         assert_eq!(forecast.predictions.len(), 5);
     }
+
+    #[test]
+    fn test_ar_recovers_coefficient() {
+        let e = shocks(2000);
+        let mut x = vec![0.0];
+        for t in 1..e.len() {
+            x.push(0.6 * x[t - 1] + e[t]);
+        }
+
+        let mut model = ARIMA::new(1, 0, 0);
+        model.fit(&TimeSeries::new(x)).unwrap();
+        assert!((model.ar_coeffs()[0] - 0.6).abs() < 0.05);
+        assert!((model.sigma2() - 1.0).abs() < 0.1);
+        assert!(model.aicc().unwrap() > model.aic().unwrap());
+    }
+
+    #[test]
+    fn test_exact_likelihood_of_ar1() {
+        let z = shocks(50);
+        let phi = 0.6;
+
+        // Closed form: the first observation has variance σ² / (1 - φ²)
+        let sum_squares = (1.0 - phi * phi) * z[0] * z[0]
+            + z.windows(2).map(|w| (w[1] - phi * w[0]).powi(2)).sum::<f64>();
+        let expected = gaussian_log_likelihood(sum_squares / 50.0, 50) + 0.5 * (1.0 - phi * phi).ln();
+        assert!((exact_log_likelihood(&z, &[phi], &[]) - expected).abs() < 1e-9);
+
+        // Rescaling shifts the likelihood by n·ln(c) whatever the order
+        let scaled: Vec<f64> = z.iter().map(|v| 0.01 * v).collect();
+        let shift = |ar: &[f64], ma: &[f64]| exact_log_likelihood(&scaled, ar, ma) - exact_log_likelihood(&z, ar, ma);
+        assert!((shift(&[], &[]) + 50.0 * 0.01f64.ln()).abs() < 1e-9);
+        assert!((shift(&[0.3, -0.2], &[0.5]) - shift(&[], &[])).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arma_css_recovers_ma() {
+        let e = shocks(2000);
+        let mut x = vec![e[0]];
+        for t in 1..e.len() {
+            x.push(0.5 * x[t - 1] + e[t] + 0.4 * e[t - 1]);
+        }
+
+        let mut model = ARIMA::new(1, 0, 1);
+        model.fit(&TimeSeries::new(x)).unwrap();
+        assert!((model.ar_coeffs()[0] - 0.5).abs() < 0.08);
+        assert!((model.ma_coeffs()[0] - 0.4).abs() < 0.08);
+    }
+
+    #[test]
+    fn test_forecast_on_original_scale() {
+        let data: Vec<f64> = (0..60).map(|x| 10.0 + 2.0 * x as f64).collect();
+        let mut model = ARIMA::new(0, 1, 0).with_constant(true);
+        model.fit(&TimeSeries::new(data)).unwrap();
+
+        let forecast = model.forecast(3).unwrap();
+        assert!((model.constant() - 2.0).abs() < 1e-9);
+        assert!((forecast.predictions[0] - 130.0).abs() < 1e-9);
+        assert!((forecast.predictions[2] - 134.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_interval_widths_grow_for_random_walk() {
+        let e = shocks(300);
+        let mut level = 0.0;
+        let data: Vec<f64> = e.iter().map(|x| { level += x; level }).collect();
+
+        let mut model = ARIMA::new(0, 1, 0);
+        model.fit(&TimeSeries::new(data)).unwrap();
+
+        let ci = model.forecast_with_levels(4, &[0.95, 0.8]).unwrap();
+        let sigma = model.sigma2().sqrt();
+        let lower = ci.lower_bound.unwrap();
+        let upper = ci.upper_bound.unwrap();
+        // Random walk: the h-step standard error is sigma * sqrt(h)
+        assert!((upper[3] - lower[3] - 2.0 * 1.959964 * sigma * 2.0).abs() < 1e-6);
+
+        let eighty = ci.intervals.iter().find(|i| i.level == 0.8).unwrap();
+        assert!((eighty.upper[0] - eighty.lower[0] - 2.0 * 1.281552 * sigma).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_pacf_transform_round_trip() {
+        let ar = pacf_to_ar(&[0.5, -0.3, 0.2]);
+        let pacf = ar_to_pacf(&ar);
+        assert!((pacf[0] - 0.5).abs() < 1e-12);
+        assert!((pacf[1] + 0.3).abs() < 1e-12);
+        assert!((pacf[2] - 0.2).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,251 @@
+//! Linear regression with ARIMA errors (ARIMAX)
+//!
+//! `yₜ = β'xₜ + nₜ` where the error `nₜ` follows an ARIMA(p,d,q) process
+//! (with the usual mean or drift). The coefficients are estimated by
+//! feasible generalised least squares on the differenced data: alternately
+//! fitting the ARIMA error model and re-estimating β on the series and
+//! regressors filtered by the inverse ARMA operator.
+
+use ndarray::Array2;
+
+use crate::{Result, TelemetryError, TimeSeries};
+use crate::forecasting::{ExogenousForecaster, ForecastResult, Forecaster};
+use crate::linalg::least_squares;
+use super::arima::{apply_difference, css_residuals, difference_polynomial, ARIMA};
+
+/// Maximum number of GLS iterations
+const MAX_ITERATIONS: usize = 20;
+
+/// Regression with ARIMA(p,d,q) errors
+#[derive(Debug, Clone)]
+pub struct ARIMAX {
+    p: usize,
+    d: usize,
+    q: usize,
+    include_constant: bool,
+    coefficients: Vec<f64>,
+    error_model: Option<ARIMA>,
+}
+
+impl ARIMAX {
+    /// Create a regression model with ARIMA(p,d,q) errors
+    ///
+    /// As with [`ARIMA`], a constant is included by default only for `d = 0`.
+    pub fn new(p: usize, d: usize, q: usize) -> Self {
+        Self {
+            p,
+            d,
+            q,
+            include_constant: d == 0,
+            coefficients: Vec::new(),
+            error_model: None,
+        }
+    }
+
+    /// Include (or exclude) the constant of the error model
+    pub fn with_constant(mut self, include: bool) -> Self {
+        self.include_constant = include;
+        self
+    }
+
+    /// Fitted regression coefficients β, one per regressor column
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    /// Fitted ARIMA model of the regression errors
+    pub fn error_model(&self) -> Option<&ARIMA> {
+        self.error_model.as_ref()
+    }
+
+    /// Akaike information criterion, counting the regression coefficients
+    pub fn aic(&self) -> Option<f64> {
+        let model = self.error_model.as_ref()?;
+        model.aic().map(|aic| aic + 2.0 * self.coefficients.len() as f64)
+    }
+
+    fn error_arima(&self) -> ARIMA {
+        ARIMA::new(self.p, self.d, self.q).with_constant(self.include_constant)
+    }
+
+    /// Regression part `β'xₜ` for each row
+    fn regression(&self, x: &Array2<f64>) -> Vec<f64> {
+        x.rows()
+            .into_iter()
+            .map(|row| row.iter().zip(&self.coefficients).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+
+    fn check_future(&self, future: &Array2<f64>) -> Result<&ARIMA> {
+        let model = self.error_model.as_ref().ok_or_else(|| {
+            TelemetryError::ModelError("Model must be fitted before forecasting".to_string())
+        })?;
+        if future.ncols() != self.coefficients.len() {
+            return Err(TelemetryError::InvalidData(format!(
+                "Expected {} regressor columns, got {}",
+                self.coefficients.len(),
+                future.ncols()
+            )));
+        }
+        if future.iter().any(|v| v.is_nan()) {
+            return Err(TelemetryError::InvalidData(
+                "Future regressors contain missing values".to_string(),
+            ));
+        }
+        Ok(model)
+    }
+}
+
+impl ExogenousForecaster for ARIMAX {
+    fn fit_with_regressors(&mut self, ts: &TimeSeries, regressors: &Array2<f64>) -> Result<()> {
+        let k = regressors.ncols();
+        if regressors.nrows() != ts.len() || k == 0 {
+            return Err(TelemetryError::InvalidData(
+                "Regressors must have one row per observation and at least one column".to_string(),
+            ));
+        }
+        if ts.has_missing() || regressors.iter().any(|v| v.is_nan()) {
+            return Err(TelemetryError::InvalidData(
+                "ARIMAX cannot be fitted with missing values".to_string(),
+            ));
+        }
+
+        let poly = difference_polynomial(self.d, 0, 0);
+        let w = apply_difference(&ts.values, &poly);
+        let mut columns: Vec<Vec<f64>> = regressors
+            .columns()
+            .into_iter()
+            .map(|c| apply_difference(&c.to_vec(), &poly))
+            .collect();
+        if self.include_constant {
+            columns.insert(0, vec![1.0; w.len()]);
+        }
+        let offset = usize::from(self.include_constant);
+        if w.len() < columns.len() + self.p + 1 {
+            return Err(TelemetryError::InsufficientData(
+                "Insufficient data for ARIMAX model".to_string(),
+            ));
+        }
+
+        let mut ar: Vec<f64> = Vec::new();
+        let mut ma: Vec<f64> = Vec::new();
+        let mut beta: Vec<f64> = Vec::new();
+        let mut model = self.error_arima();
+
+        for _ in 0..MAX_ITERATIONS {
+            // GLS step: regress the ARMA-filtered series on the filtered regressors
+            let start = ar.len();
+            let target = &css_residuals(&w, &ar, &ma)[start..];
+            let filtered: Vec<Vec<f64>> = columns.iter().map(|c| css_residuals(c, &ar, &ma)).collect();
+            let rows: Vec<Vec<f64>> = (start..w.len())
+                .map(|t| filtered.iter().map(|c| c[t]).collect())
+                .collect();
+            let new_beta = least_squares(&rows, target)?[offset..].to_vec();
+
+            let converged = !beta.is_empty()
+                && new_beta.iter().zip(&beta).all(|(a, b): (&f64, &f64)| (a - b).abs() <= 1e-8 * (1.0 + b.abs()));
+            beta = new_beta;
+
+            // Refit the error model on yₜ - β'xₜ
+            self.coefficients = beta.clone();
+            let errors: Vec<f64> = ts.values.iter().zip(self.regression(regressors)).map(|(y, r)| y - r).collect();
+            model = self.error_arima();
+            model.fit(&TimeSeries::new(errors))?;
+
+            if converged || (self.p == 0 && self.q == 0) {
+                break;
+            }
+            ar = model.ar_coeffs().to_vec();
+            ma = model.ma_coeffs().to_vec();
+        }
+
+        self.error_model = Some(model);
+        Ok(())
+    }
+
+    fn forecast_with_regressors(&self, future: &Array2<f64>) -> Result<ForecastResult> {
+        let model = self.check_future(future)?;
+        let mut result = model.forecast(future.nrows())?;
+        for (p, r) in result.predictions.iter_mut().zip(self.regression(future)) {
+            *p += r;
+        }
+        Ok(result)
+    }
+
+    /// Intervals are those of the error model, shifted by the regression
+    /// part; uncertainty in β and in the future regressors is ignored
+    fn forecast_with_regressors_confidence(&self, future: &Array2<f64>, confidence_level: f64) -> Result<ForecastResult> {
+        let model = self.check_future(future)?;
+        let base = model.forecast(future.nrows())?;
+        let errors = model.forecast_with_confidence(future.nrows(), confidence_level)?;
+        let regression = self.regression(future);
+
+        let shift = |v: &[f64]| -> Vec<f64> { v.iter().zip(&regression).map(|(a, r)| a + r).collect() };
+        let interval = &errors.intervals[0];
+        Ok(ForecastResult::with_bounds(
+            ForecastResult { predictions: shift(&base.predictions), ..base },
+            shift(&interval.lower),
+            shift(&interval.upper),
+            confidence_level,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::shocks;
+
+    #[test]
+    fn test_arimax_recovers_coefficients() {
+        let n = 400;
+        let e = shocks(2 * n);
+        let x: Vec<f64> = e[n..].iter().map(|v| 10.0 + 3.0 * v).collect();
+
+        // AR(1) errors around a mean of 2
+        let mut noise = 0.0;
+        let y: Vec<f64> = (0..n)
+            .map(|t| {
+                noise = 0.6 * noise + 0.5 * e[t];
+                2.0 + 1.5 * x[t] + noise
+            })
+            .collect();
+
+        let regressors = Array2::from_shape_vec((n, 1), x).unwrap();
+        let mut model = ARIMAX::new(1, 0, 0);
+        model.fit_with_regressors(&TimeSeries::new(y), &regressors).unwrap();
+
+        assert!((model.coefficients()[0] - 1.5).abs() < 0.05);
+        let errors = model.error_model().unwrap();
+        assert!((errors.ar_coeffs()[0] - 0.6).abs() < 0.1);
+        assert!((errors.constant() - 2.0).abs() < 0.5);
+
+        let future = Array2::from_shape_vec((2, 1), vec![10.0, 20.0]).unwrap();
+        let forecast = model.forecast_with_regressors_confidence(&future, 0.95).unwrap();
+        assert!((forecast.predictions[1] - forecast.predictions[0] - 15.0).abs() < 1.0);
+        assert!(forecast.lower_bound.unwrap()[1] < forecast.predictions[1]);
+    }
+
+    #[test]
+    fn test_arimax_differenced_errors() {
+        let n = 300;
+        let e = shocks(2 * n);
+        let x: Vec<f64> = e[n..].to_vec();
+        let mut walk = 0.0;
+        let y: Vec<f64> = (0..n)
+            .map(|t| {
+                walk += 0.3 * e[t];
+                -2.0 * x[t] + walk
+            })
+            .collect();
+
+        let regressors = Array2::from_shape_vec((n, 1), x).unwrap();
+        let mut model = ARIMAX::new(0, 1, 0);
+        model.fit_with_regressors(&TimeSeries::new(y), &regressors).unwrap();
+        assert!((model.coefficients()[0] + 2.0).abs() < 0.05);
+
+        let wrong = Array2::zeros((3, 2));
+        assert!(model.forecast_with_regressors(&wrong).is_err());
+        assert!(model.fit_with_regressors(&TimeSeries::new(vec![1.0; 10]), &wrong).is_err());
+    }
+}
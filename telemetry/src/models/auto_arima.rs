@@ -0,0 +1,413 @@
+//! Automatic ARIMA order selection (Hyndman-Khandakar stepwise search)
+//!
+//! The differencing orders are chosen first by unit-root tests: `D` by
+//! repeated OCSB seasonal tests, then `d` by repeated KPSS tests. The ARMA orders are
+//! then found by a stepwise search that minimises AICc. Candidates are ranked
+//! by the exact likelihood, which covers the same differenced sample for
+//! every order, so the choice does not change when the data are rescaled.
+
+use std::collections::HashSet;
+
+use crate::{Result, TelemetryError, TimeSeries};
+use crate::forecasting::{Forecaster, ForecastResult};
+use crate::hypothesis::{kpss, ocsb, Deterministic};
+use super::arima::{apply_difference, difference_polynomial, ARIMA};
+use super::sarima::SARIMA;
+
+/// Upper bound on the number of models fitted during the search
+const MAX_MODELS: usize = 94;
+
+/// Best candidate so far and its fitted model
+type Best = Option<(CandidateModel, ArimaModel)>;
+
+/// Model selected by [`AutoARIMA`]
+#[derive(Debug, Clone)]
+pub enum ArimaModel {
+    NonSeasonal(ARIMA),
+    Seasonal(SARIMA),
+}
+
+impl ArimaModel {
+    /// Small-sample corrected AIC of the fitted model
+    pub fn aicc(&self) -> Option<f64> {
+        match self {
+            ArimaModel::NonSeasonal(m) => m.aicc(),
+            ArimaModel::Seasonal(m) => m.aicc(),
+        }
+    }
+}
+
+impl Forecaster for ArimaModel {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        match self {
+            ArimaModel::NonSeasonal(m) => m.fit(ts),
+            ArimaModel::Seasonal(m) => m.fit(ts),
+        }
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        match self {
+            ArimaModel::NonSeasonal(m) => m.forecast(steps),
+            ArimaModel::Seasonal(m) => m.forecast(steps),
+        }
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        match self {
+            ArimaModel::NonSeasonal(m) => m.forecast_with_confidence(steps, confidence_level),
+            ArimaModel::Seasonal(m) => m.forecast_with_confidence(steps, confidence_level),
+        }
+    }
+}
+
+/// One model evaluated during the search
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateModel {
+    pub p: usize,
+    pub d: usize,
+    pub q: usize,
+    pub seasonal_p: usize,
+    pub seasonal_d: usize,
+    pub seasonal_q: usize,
+    pub include_constant: bool,
+    /// AICc of the fit, or `None` if fitting failed
+    pub aicc: Option<f64>,
+}
+
+/// ARIMA forecaster that selects its own orders when fitted
+#[derive(Debug, Clone)]
+pub struct AutoARIMA {
+    period: Option<usize>,
+    max_p: usize,
+    max_q: usize,
+    max_d: usize,
+    max_seasonal_p: usize,
+    max_seasonal_q: usize,
+    max_seasonal_d: usize,
+    model: Option<ArimaModel>,
+    trace: Vec<CandidateModel>,
+}
+
+impl Default for AutoARIMA {
+    fn default() -> Self {
+        Self {
+            period: None,
+            max_p: 5,
+            max_q: 5,
+            max_d: 2,
+            max_seasonal_p: 2,
+            max_seasonal_q: 2,
+            max_seasonal_d: 1,
+            model: None,
+            trace: Vec::new(),
+        }
+    }
+}
+
+impl AutoARIMA {
+    /// Create a non-seasonal search with default bounds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also search seasonal orders for the given season length
+    pub fn with_seasonal_period(mut self, period: usize) -> Result<Self> {
+        if period < 2 {
+            return Err(TelemetryError::InvalidParameter(
+                "Seasonal period must be at least 2".to_string(),
+            ));
+        }
+        self.period = Some(period);
+        Ok(self)
+    }
+
+    /// Maximum non-seasonal orders searched
+    pub fn with_max_order(mut self, max_p: usize, max_d: usize, max_q: usize) -> Self {
+        self.max_p = max_p;
+        self.max_d = max_d;
+        self.max_q = max_q;
+        self
+    }
+
+    /// Maximum seasonal orders searched
+    pub fn with_max_seasonal_order(mut self, max_p: usize, max_d: usize, max_q: usize) -> Self {
+        self.max_seasonal_p = max_p;
+        self.max_seasonal_d = max_d;
+        self.max_seasonal_q = max_q;
+        self
+    }
+
+    /// The selected, fitted model
+    pub fn model(&self) -> Option<&ArimaModel> {
+        self.model.as_ref()
+    }
+
+    /// Every model evaluated during the last search, in evaluation order
+    pub fn trace(&self) -> &[CandidateModel] {
+        &self.trace
+    }
+
+    /// The selected candidate (orders and AICc)
+    pub fn best(&self) -> Option<&CandidateModel> {
+        self.trace
+            .iter()
+            .filter(|c| c.aicc.is_some())
+            .min_by(|a, b| a.aicc.unwrap().total_cmp(&b.aicc.unwrap()))
+    }
+
+    /// Choose the seasonal differencing order by repeated OCSB tests
+    fn select_seasonal_d(&self, values: &[f64]) -> usize {
+        let period = match self.period {
+            Some(p) => p,
+            None => return 0,
+        };
+
+        let mut x = values.to_vec();
+        let mut seasonal_d = 0;
+        while seasonal_d < self.max_seasonal_d
+            && ocsb(&TimeSeries::new(x.clone()), period, None).is_ok_and(|r| !r.rejects(0.05))
+        {
+            x = apply_difference(&x, &difference_polynomial(0, 1, period));
+            seasonal_d += 1;
+        }
+        seasonal_d
+    }
+
+    /// Choose the differencing order by repeated KPSS tests
+    fn select_d(&self, values: &[f64]) -> usize {
+        let mut x = values.to_vec();
+        let mut d = 0;
+        while d < self.max_d
+            && x.len() > 3
+            && kpss(&TimeSeries::new(x.clone()), Deterministic::Constant, None).is_ok_and(|r| r.rejects(0.05))
+        {
+            x = apply_difference(&x, &difference_polynomial(1, 0, 0));
+            d += 1;
+        }
+        d
+    }
+
+    /// Fit one candidate and record it in the trace
+    fn evaluate(&mut self, ts: &TimeSeries, candidate: &mut CandidateModel) -> Option<ArimaModel> {
+        let c = candidate.clone();
+        let mut model = match self.period {
+            Some(period) => {
+                let sarima = SARIMA::new(c.p, c.d, c.q, c.seasonal_p, c.seasonal_d, c.seasonal_q, period).ok()?;
+                ArimaModel::Seasonal(sarima.with_constant(c.include_constant))
+            }
+            None => {
+                let arima = ARIMA::new(c.p, c.d, c.q).with_constant(c.include_constant);
+                ArimaModel::NonSeasonal(arima)
+            }
+        };
+
+        let fitted = model.fit(ts).ok().and_then(|_| model.aicc()).filter(|a| a.is_finite());
+        candidate.aicc = fitted;
+        self.trace.push(candidate.clone());
+        fitted.map(|_| model)
+    }
+}
+
+impl Forecaster for AutoARIMA {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        if ts.len() < 8 {
+            return Err(TelemetryError::InsufficientData(
+                "Need at least 8 data points for automatic ARIMA".to_string(),
+            ));
+        }
+        if ts.has_missing() {
+            return Err(TelemetryError::InvalidData(
+                "Automatic ARIMA cannot be fitted on a series with missing values".to_string(),
+            ));
+        }
+
+        self.trace.clear();
+        self.model = None;
+
+        let seasonal_d = self.select_seasonal_d(&ts.values);
+        let seasonally_differenced = match self.period {
+            Some(period) => apply_difference(&ts.values, &difference_polynomial(0, seasonal_d, period)),
+            None => ts.values.clone(),
+        };
+        let d = self.select_d(&seasonally_differenced);
+        let allow_constant = d + seasonal_d <= 1;
+        let seasonal = self.period.is_some();
+
+        let candidate = |p: usize, q: usize, sp: usize, sq: usize, constant: bool| CandidateModel {
+            p,
+            d,
+            q,
+            seasonal_p: if seasonal { sp } else { 0 },
+            seasonal_d,
+            seasonal_q: if seasonal { sq } else { 0 },
+            include_constant: constant && allow_constant,
+            aicc: None,
+        };
+        let key = |c: &CandidateModel| (c.p, c.q, c.seasonal_p, c.seasonal_q, c.include_constant);
+
+        let (sp1, sq1) = (1.min(self.max_seasonal_p), 1.min(self.max_seasonal_q));
+        let initial = vec![
+            candidate(2.min(self.max_p), 2.min(self.max_q), sp1, sq1, true),
+            candidate(0, 0, 0, 0, true),
+            candidate(1.min(self.max_p), 0, sp1, 0, true),
+            candidate(0, 1.min(self.max_q), 0, sq1, true),
+            candidate(0, 0, 0, 0, false),
+        ];
+
+        let mut visited = HashSet::new();
+        let mut best: Best = None;
+        let mut consider = |this: &mut Self, mut c: CandidateModel, best: &mut Best| {
+            if !visited.insert(key(&c)) || this.trace.len() >= MAX_MODELS {
+                return false;
+            }
+            match this.evaluate(ts, &mut c) {
+                Some(model) if best.as_ref().is_none_or(|(b, _)| c.aicc < b.aicc) => {
+                    *best = Some((c, model));
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        for c in initial {
+            consider(self, c, &mut best);
+        }
+
+        while let Some(current) = best.as_ref().map(|(c, _)| c.clone()) {
+            let (p, q) = (current.p as i64, current.q as i64);
+            let (sp, sq) = (current.seasonal_p as i64, current.seasonal_q as i64);
+            let constant = current.include_constant;
+
+            let mut neighbours = Vec::new();
+            for (dp, dq) in [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, 1), (-1, 1), (1, -1)] {
+                neighbours.push((p + dp, q + dq, sp, sq, constant));
+            }
+            if seasonal {
+                for (dp, dq) in [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, 1)] {
+                    neighbours.push((p, q, sp + dp, sq + dq, constant));
+                }
+            }
+            if allow_constant {
+                neighbours.push((p, q, sp, sq, !constant));
+            }
+
+            let mut improved = false;
+            for (p, q, sp, sq, constant) in neighbours {
+                let in_bounds = (0..=self.max_p as i64).contains(&p)
+                    && (0..=self.max_q as i64).contains(&q)
+                    && (0..=self.max_seasonal_p as i64).contains(&sp)
+                    && (0..=self.max_seasonal_q as i64).contains(&sq);
+                if !in_bounds {
+                    continue;
+                }
+
+                let next = candidate(p as usize, q as usize, sp as usize, sq as usize, constant);
+                if consider(self, next, &mut best) {
+                    improved = true;
+                    break;
+                }
+            }
+
+            if !improved || self.trace.len() >= MAX_MODELS {
+                break;
+            }
+        }
+
+        match best {
+            Some((_, model)) => {
+                self.model = Some(model);
+                Ok(())
+            }
+            None => Err(TelemetryError::ModelError(
+                "No candidate ARIMA model could be fitted".to_string(),
+            )),
+        }
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        self.model
+            .as_ref()
+            .ok_or_else(|| TelemetryError::ModelError(
+                "Model must be fitted before forecasting".to_string()
+            ))?
+            .forecast(steps)
+    }
+
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        self.model
+            .as_ref()
+            .ok_or_else(|| TelemetryError::ModelError(
+                "Model must be fitted before forecasting".to_string()
+            ))?
+            .forecast_with_confidence(steps, confidence_level)
+    }
+}
+
+/// Select and fit an ARIMA (or SARIMA, when `period` is given) model automatically
+pub fn auto_arima(ts: &TimeSeries, period: Option<usize>) -> Result<AutoARIMA> {
+    let mut model = AutoARIMA::new();
+    if let Some(period) = period {
+        model = model.with_seasonal_period(period)?;
+    }
+    model.fit(ts)?;
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::seeded_shocks;
+
+    #[test]
+    fn test_selects_differencing_for_random_walk() {
+        let mut level = 0.0;
+        let data: Vec<f64> = seeded_shocks(300, 11).iter().map(|e| { level += e; level }).collect();
+
+        let model = auto_arima(&TimeSeries::new(data), None).unwrap();
+        let best = model.best().unwrap();
+        assert_eq!(best.d, 1);
+        assert!(model.trace().len() >= 5);
+        assert_eq!(model.forecast(4).unwrap().predictions.len(), 4);
+    }
+
+    #[test]
+    fn test_selects_ar_for_stationary_series() {
+        let e = seeded_shocks(400, 11);
+        let mut x = vec![0.0];
+        for t in 1..e.len() {
+            x.push(0.7 * x[t - 1] + e[t]);
+        }
+
+        let model = auto_arima(&TimeSeries::new(x), None).unwrap();
+        let best = model.best().unwrap();
+        assert_eq!(best.d, 0);
+        assert!(best.p + best.q >= 1);
+    }
+
+    #[test]
+    fn test_selects_white_noise_at_any_scale() {
+        let e = seeded_shocks(200, 2);
+        let scaled: Vec<f64> = e.iter().map(|v| 0.01 * v).collect();
+
+        for period in [None, Some(12)] {
+            let orders = |values: &[f64]| {
+                let model = auto_arima(&TimeSeries::new(values.to_vec()), period).unwrap();
+                let best = model.best().unwrap();
+                (best.p, best.d, best.q, best.seasonal_p, best.seasonal_d, best.seasonal_q)
+            };
+            assert_eq!(orders(&e), (0, 0, 0, 0, 0, 0));
+            assert_eq!(orders(&scaled), orders(&e));
+        }
+    }
+
+    #[test]
+    fn test_seasonal_differencing() {
+        let pattern = [10.0, -4.0, 3.0, -9.0];
+        let e = seeded_shocks(120, 11);
+        let data: Vec<f64> = (0..120).map(|i| pattern[i % 4] + 0.3 * e[i]).collect();
+
+        let model = auto_arima(&TimeSeries::new(data), Some(4)).unwrap();
+        assert_eq!(model.best().unwrap().seasonal_d, 1);
+        assert!(matches!(model.model(), Some(ArimaModel::Seasonal(_))));
+    }
+}
@@ -0,0 +1,482 @@
+//! Vector autoregression: VAR(p)
+//!
+//! `yₜ = c + A₁yₜ₋₁ + … + Aₚyₜ₋ₚ + uₜ` for a k-dimensional series, estimated
+//! equation by equation with ordinary least squares. Multivariate data is
+//! passed as an `Array2<f64>` with one row per time step and one column per
+//! variable, e.g. from [`crate::MultiTimeSeries::to_array2`].
+
+use ndarray::{s, Array2};
+use statrs::distribution::{ContinuousCDF, FisherSnedecor};
+
+use crate::{Result, TelemetryError};
+use crate::forecasting::{z_score, ForecastResult, PredictionInterval};
+use crate::linalg::{cholesky, least_squares};
+
+/// Information criterion used to select the lag order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InformationCriterion {
+    /// Akaike
+    AIC,
+    /// Schwarz (Bayesian)
+    BIC,
+    /// Hannan-Quinn
+    HQ,
+}
+
+/// Multivariate forecast: one row per horizon, one column per variable
+#[derive(Debug, Clone)]
+pub struct VARForecast {
+    /// Point forecasts
+    pub predictions: Array2<f64>,
+    /// Forecast standard errors from the forecast error covariance
+    pub std_errors: Array2<f64>,
+    /// Lower bounds (if requested)
+    pub lower_bound: Option<Array2<f64>>,
+    /// Upper bounds (if requested)
+    pub upper_bound: Option<Array2<f64>>,
+    /// Confidence level of the bounds
+    pub confidence: f64,
+}
+
+impl VARForecast {
+    /// Univariate forecast of variable `j`
+    pub fn variable(&self, j: usize) -> Option<ForecastResult> {
+        if j >= self.predictions.ncols() {
+            return None;
+        }
+
+        let column = |m: &Array2<f64>| m.column(j).to_vec();
+        let (lower, upper) = (self.lower_bound.as_ref().map(column), self.upper_bound.as_ref().map(column));
+        let intervals = match (&lower, &upper) {
+            (Some(l), Some(u)) => vec![PredictionInterval { level: self.confidence, lower: l.clone(), upper: u.clone() }],
+            _ => Vec::new(),
+        };
+        Some(ForecastResult {
+            predictions: column(&self.predictions),
+            lower_bound: lower,
+            upper_bound: upper,
+            confidence: self.confidence,
+            intervals,
+        })
+    }
+}
+
+/// Result of a Granger-causality F-test
+#[derive(Debug, Clone, Copy)]
+pub struct GrangerTest {
+    /// F statistic
+    pub statistic: f64,
+    /// Numerator and denominator degrees of freedom
+    pub df: (usize, usize),
+    /// p-value under the null hypothesis of no Granger causality
+    pub p_value: f64,
+}
+
+/// Vector autoregressive model of order p
+#[derive(Debug, Clone)]
+pub struct VAR {
+    p: usize,
+    intercept: Vec<f64>,
+    /// `coefficients[i]` is the k×k matrix A_{i+1}
+    coefficients: Vec<Array2<f64>>,
+    sigma: Array2<f64>,
+    residuals: Array2<f64>,
+    data: Array2<f64>,
+    fitted: bool,
+}
+
+impl VAR {
+    /// Create a VAR(p) model (with intercept)
+    pub fn new(p: usize) -> Result<Self> {
+        if p == 0 {
+            return Err(TelemetryError::InvalidParameter(
+                "VAR order must be at least 1".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            p,
+            intercept: Vec::new(),
+            coefficients: Vec::new(),
+            sigma: Array2::zeros((0, 0)),
+            residuals: Array2::zeros((0, 0)),
+            data: Array2::zeros((0, 0)),
+            fitted: false,
+        })
+    }
+
+    /// Lag order
+    pub fn order(&self) -> usize {
+        self.p
+    }
+
+    /// Intercept vector c
+    pub fn intercept(&self) -> &[f64] {
+        &self.intercept
+    }
+
+    /// Coefficient matrices A₁..Aₚ; `A[i][(r, c)]` is the effect of variable
+    /// `c` at lag `i + 1` on variable `r`
+    pub fn coefficients(&self) -> &[Array2<f64>] {
+        &self.coefficients
+    }
+
+    /// Residual covariance Σᵤ (degrees-of-freedom adjusted)
+    pub fn sigma(&self) -> &Array2<f64> {
+        &self.sigma
+    }
+
+    /// In-sample residuals, one row per effective observation
+    pub fn residuals(&self) -> &Array2<f64> {
+        &self.residuals
+    }
+
+    /// Akaike information criterion `ln|Σ̃ᵤ| + 2pk²/T`
+    pub fn aic(&self) -> Option<f64> {
+        self.information_criterion(InformationCriterion::AIC)
+    }
+
+    /// Schwarz criterion `ln|Σ̃ᵤ| + ln(T)pk²/T`
+    pub fn bic(&self) -> Option<f64> {
+        self.information_criterion(InformationCriterion::BIC)
+    }
+
+    /// Hannan-Quinn criterion `ln|Σ̃ᵤ| + 2ln(ln T)pk²/T`
+    pub fn hqic(&self) -> Option<f64> {
+        self.information_criterion(InformationCriterion::HQ)
+    }
+
+    /// Information criterion with the maximum-likelihood residual covariance Σ̃ᵤ
+    pub fn information_criterion(&self, criterion: InformationCriterion) -> Option<f64> {
+        if !self.fitted {
+            return None;
+        }
+
+        let t = self.residuals.nrows() as f64;
+        let k = self.k();
+        let sigma_ml = self.residuals.t().dot(&self.residuals) / t;
+        let chol = cholesky(&to_rows(&sigma_ml)).ok()?;
+        let log_det: f64 = (0..k).map(|i| 2.0 * chol[i][i].ln()).sum();
+
+        let params = (self.p * k * k) as f64;
+        let penalty = match criterion {
+            InformationCriterion::AIC => 2.0,
+            InformationCriterion::BIC => t.ln(),
+            InformationCriterion::HQ => 2.0 * t.ln().ln(),
+        };
+        Some(log_det + penalty * params / t)
+    }
+
+    fn k(&self) -> usize {
+        self.data.ncols()
+    }
+
+    fn check_fitted(&self) -> Result<()> {
+        if !self.fitted {
+            return Err(TelemetryError::ModelError(
+                "Model must be fitted before use".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Design matrix rows `[1, yₜ₋₁, …, yₜ₋ₚ]` for t = p..n
+    fn design(data: &Array2<f64>, p: usize) -> Vec<Vec<f64>> {
+        let k = data.ncols();
+        (p..data.nrows())
+            .map(|t| {
+                let mut row = Vec::with_capacity(1 + k * p);
+                row.push(1.0);
+                for lag in 1..=p {
+                    row.extend(data.row(t - lag).iter());
+                }
+                row
+            })
+            .collect()
+    }
+
+    /// Fit by OLS, one equation per variable
+    pub fn fit(&mut self, data: &Array2<f64>) -> Result<()> {
+        let (n, k) = data.dim();
+        if k == 0 {
+            return Err(TelemetryError::InvalidData(
+                "Multivariate data must have at least one column".to_string(),
+            ));
+        }
+        if n < self.p + 1 + k * self.p + 1 {
+            return Err(TelemetryError::InsufficientData(format!(
+                "Need at least {} observations for a {}-variable VAR({})",
+                self.p + 2 + k * self.p,
+                k,
+                self.p
+            )));
+        }
+        if data.iter().any(|v| v.is_nan()) {
+            return Err(TelemetryError::InvalidData(
+                "VAR cannot be fitted on data with missing values".to_string(),
+            ));
+        }
+
+        let design = Self::design(data, self.p);
+        let t = design.len();
+        let mut intercept = vec![0.0; k];
+        let mut coefficients = vec![Array2::zeros((k, k)); self.p];
+        let mut residuals = Array2::zeros((t, k));
+
+        for eq in 0..k {
+            let y: Vec<f64> = data.slice(s![self.p.., eq]).to_vec();
+            let beta = least_squares(&design, &y)?;
+            intercept[eq] = beta[0];
+            for (lag, a) in coefficients.iter_mut().enumerate() {
+                for var in 0..k {
+                    a[(eq, var)] = beta[1 + lag * k + var];
+                }
+            }
+            for (i, row) in design.iter().enumerate() {
+                let fitted: f64 = row.iter().zip(&beta).map(|(x, b)| x * b).sum();
+                residuals[(i, eq)] = y[i] - fitted;
+            }
+        }
+
+        let dof = (t - k * self.p - 1) as f64;
+        self.sigma = residuals.t().dot(&residuals) / dof;
+        self.intercept = intercept;
+        self.coefficients = coefficients;
+        self.residuals = residuals;
+        self.data = data.clone();
+        self.fitted = true;
+        Ok(())
+    }
+
+    /// Point forecasts `steps` ahead
+    pub fn forecast(&self, steps: usize) -> Result<VARForecast> {
+        self.check_fitted()?;
+
+        let k = self.k();
+        let n = self.data.nrows();
+        let mut path: Vec<Vec<f64>> = (n - self.p..n).map(|t| self.data.row(t).to_vec()).collect();
+        let mut predictions = Array2::zeros((steps, k));
+        for h in 0..steps {
+            let mut next = self.intercept.clone();
+            for (lag, a) in self.coefficients.iter().enumerate() {
+                let past = &path[path.len() - 1 - lag];
+                for (r, value) in next.iter_mut().enumerate() {
+                    *value += (0..k).map(|c| a[(r, c)] * past[c]).sum::<f64>();
+                }
+            }
+            for (j, v) in next.iter().enumerate() {
+                predictions[(h, j)] = *v;
+            }
+            path.push(next);
+        }
+
+        // Forecast error covariance Σ(h) = Σ_{i<h} Φᵢ Σᵤ Φᵢᵀ
+        let mut std_errors = Array2::zeros((steps, k));
+        let mut mse: Array2<f64> = Array2::zeros((k, k));
+        for (h, phi) in self.ma_matrices(steps).iter().enumerate() {
+            mse = mse + phi.dot(&self.sigma).dot(&phi.t());
+            for j in 0..k {
+                std_errors[(h, j)] = mse[(j, j)].sqrt();
+            }
+        }
+
+        Ok(VARForecast {
+            predictions,
+            std_errors,
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.0,
+        })
+    }
+
+    /// Forecasts with Gaussian intervals from the forecast error covariance
+    pub fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<VARForecast> {
+        let z = z_score(confidence_level)?;
+        let mut forecast = self.forecast(steps)?;
+        forecast.lower_bound = Some(&forecast.predictions - &(&forecast.std_errors * z));
+        forecast.upper_bound = Some(&forecast.predictions + &(&forecast.std_errors * z));
+        forecast.confidence = confidence_level;
+        Ok(forecast)
+    }
+
+    /// MA(∞) coefficient matrices Φ₀ = I, Φᵢ = Σⱼ Φᵢ₋ⱼ Aⱼ
+    fn ma_matrices(&self, count: usize) -> Vec<Array2<f64>> {
+        let k = self.k();
+        let mut phis: Vec<Array2<f64>> = Vec::with_capacity(count);
+        for i in 0..count {
+            let phi = if i == 0 {
+                Array2::eye(k)
+            } else {
+                (1..=i.min(self.p)).fold(Array2::zeros((k, k)), |acc, j| {
+                    acc + phis[i - j].dot(&self.coefficients[j - 1])
+                })
+            };
+            phis.push(phi);
+        }
+        phis
+    }
+
+    /// Impulse responses for horizons 0..=`horizon`
+    ///
+    /// Element `(i, j)` of the h-th matrix is the response of variable `i`
+    /// after h steps to a shock in variable `j`. Orthogonalised responses use
+    /// one-standard-deviation shocks from the Cholesky factor of Σᵤ, so they
+    /// depend on the column order.
+    pub fn impulse_response(&self, horizon: usize, orthogonalized: bool) -> Result<Vec<Array2<f64>>> {
+        self.check_fitted()?;
+
+        let phis = self.ma_matrices(horizon + 1);
+        if !orthogonalized {
+            return Ok(phis);
+        }
+
+        let k = self.k();
+        let chol = cholesky(&to_rows(&self.sigma))?;
+        let p_matrix = Array2::from_shape_fn((k, k), |(i, j)| chol[i][j]);
+        Ok(phis.iter().map(|phi| phi.dot(&p_matrix)).collect())
+    }
+
+    /// F-test of whether the lags of variable `cause` help predict `effect`
+    pub fn granger_causality(&self, cause: usize, effect: usize) -> Result<GrangerTest> {
+        self.check_fitted()?;
+        let k = self.k();
+        if cause >= k || effect >= k || cause == effect {
+            return Err(TelemetryError::InvalidParameter(
+                "Cause and effect must be distinct variable indices".to_string(),
+            ));
+        }
+
+        let design = Self::design(&self.data, self.p);
+        let y: Vec<f64> = self.data.slice(s![self.p.., effect]).to_vec();
+        let rss = |rows: &[Vec<f64>]| -> Result<f64> {
+            let beta = least_squares(rows, &y)?;
+            Ok(rows
+                .iter()
+                .zip(&y)
+                .map(|(row, yi)| (yi - row.iter().zip(&beta).map(|(x, b)| x * b).sum::<f64>()).powi(2))
+                .sum())
+        };
+
+        let restricted: Vec<Vec<f64>> = design
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i == 0 || (i - 1) % k != cause)
+                    .map(|(_, v)| *v)
+                    .collect()
+            })
+            .collect();
+
+        let rss_u = rss(&design)?;
+        let rss_r = rss(&restricted)?;
+        let df1 = self.p;
+        let df2 = design.len() - design[0].len();
+        let statistic = ((rss_r - rss_u) / df1 as f64) / (rss_u / df2 as f64);
+        let f = FisherSnedecor::new(df1 as f64, df2 as f64).map_err(|e| TelemetryError::ModelError(e.to_string()))?;
+
+        Ok(GrangerTest {
+            statistic,
+            df: (df1, df2),
+            p_value: 1.0 - f.cdf(statistic),
+        })
+    }
+}
+
+fn to_rows(m: &Array2<f64>) -> Vec<Vec<f64>> {
+    m.rows().into_iter().map(|r| r.to_vec()).collect()
+}
+
+/// Fit VAR(1)..VAR(`max_p`) on a common estimation sample and return the
+/// order minimising `criterion`, refitted on all the data
+pub fn auto_var(data: &Array2<f64>, max_p: usize, criterion: InformationCriterion) -> Result<VAR> {
+    if max_p == 0 {
+        return Err(TelemetryError::InvalidParameter(
+            "Maximum VAR order must be at least 1".to_string(),
+        ));
+    }
+
+    let mut best: Option<(usize, f64)> = None;
+    for p in 1..=max_p {
+        let mut model = VAR::new(p)?;
+        if model.fit(&data.slice(s![max_p - p.., ..]).to_owned()).is_err() {
+            continue;
+        }
+        if let Some(ic) = model.information_criterion(criterion) {
+            if best.is_none_or(|(_, b)| ic < b) {
+                best = Some((p, ic));
+            }
+        }
+    }
+
+    let (p, _) = best.ok_or_else(|| {
+        TelemetryError::InsufficientData("No VAR order could be fitted".to_string())
+    })?;
+    let mut model = VAR::new(p)?;
+    model.fit(data)?;
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::shocks;
+
+    /// Bivariate VAR(1): y1 depends on lagged y2, y2 is autonomous
+    fn simulate(n: usize) -> Array2<f64> {
+        let e = shocks(2 * n + 100);
+        let mut y = [0.0, 0.0];
+        let mut data = Array2::zeros((n, 2));
+        for t in 0..n + 50 {
+            let y1 = 1.0 + 0.5 * y[0] + 0.3 * y[1] + e[2 * t];
+            let y2 = -0.5 + 0.6 * y[1] + 0.5 * e[2 * t + 1] + 0.3 * e[2 * t];
+            y = [y1, y2];
+            if t >= 50 {
+                data[(t - 50, 0)] = y1;
+                data[(t - 50, 1)] = y2;
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_var_fit_and_forecast() {
+        let data = simulate(500);
+        let mut model = VAR::new(1).unwrap();
+        model.fit(&data).unwrap();
+
+        let a = &model.coefficients()[0];
+        assert!((a[(0, 0)] - 0.5).abs() < 0.1);
+        assert!((a[(0, 1)] - 0.3).abs() < 0.1);
+        assert!(a[(1, 0)].abs() < 0.1);
+        assert!((a[(1, 1)] - 0.6).abs() < 0.1);
+
+        let forecast = model.forecast_with_confidence(20, 0.95).unwrap();
+        assert_eq!(forecast.predictions.dim(), (20, 2));
+        // Long-run mean of y2 is -0.5 / 0.4 = -1.25
+        assert!((forecast.predictions[(19, 1)] + 1.25).abs() < 0.3);
+        assert!(forecast.std_errors[(19, 0)] > forecast.std_errors[(0, 0)]);
+        let y1 = forecast.variable(0).unwrap();
+        assert!(y1.lower_bound.unwrap()[0] < y1.predictions[0]);
+    }
+
+    #[test]
+    fn test_var_granger_and_irf() {
+        let data = simulate(500);
+        let model = auto_var(&data, 4, InformationCriterion::BIC).unwrap();
+        assert_eq!(model.order(), 1);
+
+        assert!(model.granger_causality(1, 0).unwrap().p_value < 0.01);
+        assert!(model.granger_causality(0, 1).unwrap().p_value > 0.01);
+        assert!(model.granger_causality(0, 0).is_err());
+
+        let irf = model.impulse_response(3, false).unwrap();
+        assert_eq!(irf[0], Array2::eye(2));
+        assert!((&irf[1] - &model.coefficients()[0]).iter().all(|v| v.abs() < 1e-12));
+
+        // Orthogonalised impact responses are the Cholesky factor of Σᵤ
+        let orth = model.impulse_response(0, true).unwrap();
+        assert!(orth[0][(0, 1)].abs() < 1e-12);
+        assert!((orth[0][(0, 0)] - model.sigma()[(0, 0)].sqrt()).abs() < 1e-12);
+    }
+}
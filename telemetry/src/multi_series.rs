@@ -0,0 +1,292 @@
+//! Multivariate time series sharing one timestamp index
+
+use chrono::{DateTime, Utc};
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, TelemetryError, TimeSeries};
+
+/// Named columns of equal length with optional shared timestamps
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MultiTimeSeries {
+    names: Vec<String>,
+    columns: Vec<Vec<f64>>,
+    timestamps: Option<Vec<DateTime<Utc>>>,
+}
+
+impl MultiTimeSeries {
+    /// Create an empty multivariate series
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Combine univariate series of equal length
+    ///
+    /// Columns are named after the series (`col{i}` for unnamed ones). If the
+    /// series carry timestamps they must all be identical.
+    pub fn from_series(series: Vec<TimeSeries>) -> Result<Self> {
+        let mut result = Self::new();
+        for (i, ts) in series.into_iter().enumerate() {
+            match (&result.timestamps, &ts.timestamps) {
+                (Some(a), Some(b)) if a != b => {
+                    return Err(TelemetryError::InvalidData(
+                        "All series must share the same timestamps".to_string(),
+                    ));
+                }
+                (None, Some(b)) if i == 0 => result.timestamps = Some(b.clone()),
+                (None, Some(_)) | (Some(_), None) => {
+                    return Err(TelemetryError::InvalidData(
+                        "Either all or none of the series must have timestamps".to_string(),
+                    ));
+                }
+                _ => {}
+            }
+            let name = ts.name.clone().unwrap_or_else(|| format!("col{}", i));
+            result.add_column(name, ts.values)?;
+        }
+        Ok(result)
+    }
+
+    /// Build from a matrix with one row per time step and one column per variable
+    pub fn from_array2(data: &Array2<f64>, names: Vec<String>) -> Result<Self> {
+        if names.len() != data.ncols() {
+            return Err(TelemetryError::InvalidData(
+                "Need one name per column".to_string(),
+            ));
+        }
+
+        let mut result = Self::new();
+        for (name, column) in names.into_iter().zip(data.columns()) {
+            result.add_column(name, column.to_vec())?;
+        }
+        Ok(result)
+    }
+
+    /// Attach a shared timestamp index
+    pub fn with_timestamps(mut self, timestamps: Vec<DateTime<Utc>>) -> Result<Self> {
+        if !self.columns.is_empty() && timestamps.len() != self.len() {
+            return Err(TelemetryError::InvalidData(
+                "Values and timestamps must have the same length".to_string(),
+            ));
+        }
+        self.timestamps = Some(timestamps);
+        Ok(self)
+    }
+
+    /// Append a column; its length must match the existing columns
+    pub fn add_column(&mut self, name: impl Into<String>, values: Vec<f64>) -> Result<()> {
+        let name = name.into();
+        if self.names.contains(&name) {
+            return Err(TelemetryError::InvalidParameter(
+                format!("Duplicate column name '{}'", name),
+            ));
+        }
+        let expected = match (&self.timestamps, self.columns.first()) {
+            (_, Some(first)) => Some(first.len()),
+            (Some(ts), None) => Some(ts.len()),
+            (None, None) => None,
+        };
+        if expected.is_some_and(|n| n != values.len()) {
+            return Err(TelemetryError::InvalidData(
+                "All columns must have the same length".to_string(),
+            ));
+        }
+
+        self.names.push(name);
+        self.columns.push(values);
+        Ok(())
+    }
+
+    /// Number of time steps
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, |c| c.len())
+    }
+
+    /// Check if the series has no time steps
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of columns
+    pub fn n_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Column names in order
+    pub fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Shared timestamps, if any
+    pub fn timestamps(&self) -> Option<&[DateTime<Utc>]> {
+        self.timestamps.as_deref()
+    }
+
+    fn index_of(&self, name: &str) -> Result<usize> {
+        self.names.iter().position(|n| n == name).ok_or_else(|| {
+            TelemetryError::InvalidParameter(format!("Unknown column '{}'", name))
+        })
+    }
+
+    /// Values of a column
+    pub fn values(&self, name: &str) -> Result<&[f64]> {
+        Ok(&self.columns[self.index_of(name)?])
+    }
+
+    /// A column as a named univariate series with the shared timestamps
+    pub fn column(&self, name: &str) -> Result<TimeSeries> {
+        let i = self.index_of(name)?;
+        Ok(self.column_at(i))
+    }
+
+    fn column_at(&self, i: usize) -> TimeSeries {
+        TimeSeries {
+            values: self.columns[i].clone(),
+            timestamps: self.timestamps.clone(),
+            name: Some(self.names[i].clone()),
+        }
+    }
+
+    /// Iterate over the columns as univariate series
+    pub fn iter(&self) -> impl Iterator<Item = TimeSeries> + '_ {
+        (0..self.columns.len()).map(|i| self.column_at(i))
+    }
+
+    /// Keep only the named columns, in the given order
+    pub fn select(&self, names: &[&str]) -> Result<Self> {
+        let mut result = Self {
+            timestamps: self.timestamps.clone(),
+            ..Self::new()
+        };
+        for name in names {
+            result.add_column(*name, self.values(name)?.to_vec())?;
+        }
+        Ok(result)
+    }
+
+    /// Rows `start..end`, with the same bounds rules as [`TimeSeries::slice`]
+    pub fn slice(&self, start: usize, end: usize) -> Result<Self> {
+        if start >= end || end > self.len() {
+            return Err(TelemetryError::InvalidParameter(
+                "Invalid slice indices".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            names: self.names.clone(),
+            columns: self.columns.iter().map(|c| c[start..end].to_vec()).collect(),
+            timestamps: self.timestamps.as_ref().map(|ts| ts[start..end].to_vec()),
+        })
+    }
+
+    /// Matrix with one row per time step and one column per variable
+    pub fn to_array2(&self) -> Array2<f64> {
+        Array2::from_shape_fn((self.len(), self.n_columns()), |(t, j)| self.columns[j][t])
+    }
+
+    /// Apply `f` to every column, returning `(name, result)` pairs
+    pub fn map<T, F>(&self, f: F) -> Vec<(String, T)>
+    where
+        F: Fn(&TimeSeries) -> T,
+    {
+        self.iter().map(|ts| (ts.name.clone().unwrap_or_default(), f(&ts))).collect()
+    }
+
+    /// Apply a fallible `f` to every column, stopping at the first error
+    pub fn try_map<T, F>(&self, f: F) -> Result<Vec<(String, T)>>
+    where
+        F: Fn(&TimeSeries) -> Result<T>,
+    {
+        self.iter().map(|ts| Ok((ts.name.clone().unwrap_or_default(), f(&ts)?))).collect()
+    }
+
+    /// Transform every column into a new column of the same length, keeping
+    /// names and timestamps (e.g. EMA, missing-value filling)
+    pub fn map_columns<F>(&self, f: F) -> Result<Self>
+    where
+        F: Fn(&TimeSeries) -> Result<Vec<f64>>,
+    {
+        let mut result = Self {
+            timestamps: self.timestamps.clone(),
+            ..Self::new()
+        };
+        for ts in self.iter() {
+            let values = f(&ts)?;
+            if values.len() != ts.len() {
+                return Err(TelemetryError::InvalidData(
+                    "Column transformation must preserve the length".to_string(),
+                ));
+            }
+            result.add_column(ts.name.unwrap_or_default(), values)?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn sample() -> MultiTimeSeries {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let timestamps: Vec<_> = (0..5).map(|i| start + Duration::hours(i)).collect();
+        let depth_10 = TimeSeries::with_timestamps(vec![1.0, 2.0, 3.0, 4.0, 5.0], timestamps.clone())
+            .unwrap()
+            .with_name("depth_10");
+        let depth_30 = TimeSeries::with_timestamps(vec![5.0, 4.0, 3.0, 2.0, 1.0], timestamps)
+            .unwrap()
+            .with_name("depth_30");
+        MultiTimeSeries::from_series(vec![depth_10, depth_30]).unwrap()
+    }
+
+    #[test]
+    fn test_construction_and_selection() {
+        let mts = sample();
+        assert_eq!(mts.len(), 5);
+        assert_eq!(mts.column_names(), &["depth_10".to_string(), "depth_30".to_string()]);
+
+        let column = mts.column("depth_30").unwrap();
+        assert_eq!(column.values[0], 5.0);
+        assert_eq!(column.timestamps.as_ref().unwrap().len(), 5);
+
+        let selected = mts.select(&["depth_30"]).unwrap();
+        assert_eq!(selected.n_columns(), 1);
+        assert!(mts.select(&["missing"]).is_err());
+
+        let sliced = mts.slice(1, 3).unwrap();
+        assert_eq!(sliced.values("depth_10").unwrap(), &[2.0, 3.0]);
+        assert_eq!(sliced.timestamps().unwrap()[0], mts.timestamps().unwrap()[1]);
+        assert!(mts.slice(3, 3).is_err());
+
+        let mut bad = mts.clone();
+        assert!(bad.add_column("short", vec![1.0]).is_err());
+        assert!(bad.add_column("depth_10", vec![0.0; 5]).is_err());
+    }
+
+    #[test]
+    fn test_array_round_trip() {
+        let mts = sample();
+        let array = mts.to_array2();
+        assert_eq!(array.dim(), (5, 2));
+        assert_eq!(array[(0, 1)], 5.0);
+
+        let back = MultiTimeSeries::from_array2(&array, mts.column_names().to_vec()).unwrap();
+        assert_eq!(back.values("depth_30").unwrap(), mts.values("depth_30").unwrap());
+        assert!(MultiTimeSeries::from_array2(&array, vec!["a".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_map_columns() {
+        let mts = sample();
+        let means = mts.map(|ts| ts.statistics().mean);
+        assert_eq!(means[1], ("depth_30".to_string(), 3.0));
+
+        let windows = mts.try_map(|ts| ts.moving_average(2)).unwrap();
+        assert_eq!(windows[0].1.len(), 4);
+
+        let smoothed = mts.map_columns(|ts| ts.exponential_moving_average(0.5)).unwrap();
+        assert_eq!(smoothed.n_columns(), 2);
+        assert!(mts.map_columns(|ts| ts.moving_average(2)).is_err());
+    }
+}
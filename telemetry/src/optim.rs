@@ -0,0 +1,123 @@
+//! Derivative-free numerical optimisation used for model fitting
+
+/// Minimise `f` with the Nelder-Mead simplex method.
+///
+/// `step` is the initial simplex size along each coordinate. Returns the best
+/// point found and its objective value. Non-finite objective values are
+/// treated as +infinity so callers can return `NaN` for invalid regions.
+pub(crate) fn nelder_mead<F>(f: F, x0: &[f64], step: f64, max_iter: usize, tol: f64) -> (Vec<f64>, f64)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let eval = |x: &[f64]| {
+        let v = f(x);
+        if v.is_finite() { v } else { f64::INFINITY }
+    };
+
+    let n = x0.len();
+    if n == 0 {
+        return (Vec::new(), eval(x0));
+    }
+
+    let mut simplex: Vec<Vec<f64>> = Vec::with_capacity(n + 1);
+    simplex.push(x0.to_vec());
+    for i in 0..n {
+        let mut x = x0.to_vec();
+        x[i] += step;
+        simplex.push(x);
+    }
+    let mut values: Vec<f64> = simplex.iter().map(|x| eval(x)).collect();
+
+    for _ in 0..max_iter {
+        let mut order: Vec<usize> = (0..=n).collect();
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+        simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+
+        if (values[n] - values[0]).abs() <= tol * (values[0].abs() + tol) {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|x| x[j]).sum::<f64>() / n as f64)
+            .collect();
+        let along = |t: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(simplex[n].iter())
+                .map(|(c, w)| c + t * (w - c))
+                .collect()
+        };
+
+        let reflected = along(-1.0);
+        let fr = eval(&reflected);
+
+        if fr < values[0] {
+            let expanded = along(-2.0);
+            let fe = eval(&expanded);
+            if fe < fr {
+                simplex[n] = expanded;
+                values[n] = fe;
+            } else {
+                simplex[n] = reflected;
+                values[n] = fr;
+            }
+        } else if fr < values[n - 1] {
+            simplex[n] = reflected;
+            values[n] = fr;
+        } else {
+            let contracted = if fr < values[n] { along(-0.5) } else { along(0.5) };
+            let fc = eval(&contracted);
+            if fc < values[n].min(fr) {
+                simplex[n] = contracted;
+                values[n] = fc;
+            } else {
+                // Shrink towards the best vertex
+                for i in 1..=n {
+                    simplex[i] = simplex[0]
+                        .iter()
+                        .zip(simplex[i].iter())
+                        .map(|(b, x)| b + 0.5 * (x - b))
+                        .collect();
+                    values[i] = eval(&simplex[i]);
+                }
+            }
+        }
+    }
+
+    let best = (0..=n)
+        .min_by(|&a, &b| values[a].total_cmp(&values[b]))
+        .unwrap();
+    (simplex[best].clone(), values[best])
+}
+
+/// Map an unconstrained value into the open interval (lower, upper)
+pub(crate) fn to_interval(x: f64, lower: f64, upper: f64) -> f64 {
+    lower + (upper - lower) / (1.0 + (-x).exp())
+}
+
+/// Inverse of [`to_interval`]
+pub(crate) fn from_interval(y: f64, lower: f64, upper: f64) -> f64 {
+    let p = ((y - lower) / (upper - lower)).clamp(1e-6, 1.0 - 1e-6);
+    (p / (1.0 - p)).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nelder_mead_quadratic() {
+        let f = |x: &[f64]| (x[0] - 3.0).powi(2) + 2.0 * (x[1] + 1.0).powi(2);
+        let (x, fx) = nelder_mead(f, &[0.0, 0.0], 0.5, 1000, 1e-12);
+        assert!((x[0] - 3.0).abs() < 1e-4);
+        assert!((x[1] + 1.0).abs() < 1e-4);
+        assert!(fx < 1e-8);
+    }
+
+    #[test]
+    fn test_interval_round_trip() {
+        let y = to_interval(from_interval(0.3, 0.0, 1.0), 0.0, 1.0);
+        assert!((y - 0.3).abs() < 1e-12);
+    }
+}
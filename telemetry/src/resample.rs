@@ -0,0 +1,235 @@
+//! Timestamp-aware resampling of irregular time series
+
+use crate::time_series::quantile_sorted;
+use crate::{Result, TelemetryError, TimeSeries};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+
+/// Target frequency for resampling
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    /// Fixed-width buckets anchored at the Unix epoch (e.g. 15 minutes)
+    Fixed(Duration),
+    /// One bucket per calendar minute
+    Minute,
+    /// One bucket per calendar hour
+    Hour,
+    /// One bucket per calendar day (UTC)
+    Day,
+    /// One bucket per ISO week, starting on Monday
+    Week,
+    /// One bucket per calendar month
+    Month,
+}
+
+impl Frequency {
+    /// Start of the bucket containing `t`
+    pub fn floor(&self, t: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Frequency::Fixed(d) => floor_fixed(t, *d),
+            Frequency::Minute => floor_fixed(t, Duration::minutes(1)),
+            Frequency::Hour => floor_fixed(t, Duration::hours(1)),
+            Frequency::Day => floor_fixed(t, Duration::days(1)),
+            Frequency::Week => {
+                let day = floor_fixed(t, Duration::days(1));
+                day - Duration::days(t.weekday().num_days_from_monday() as i64)
+            }
+            Frequency::Month => Utc
+                .with_ymd_and_hms(t.year(), t.month(), 1, 0, 0, 0)
+                .unwrap(),
+        }
+    }
+
+    /// Start of the bucket following the one starting at `start`
+    pub fn next(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Frequency::Fixed(d) => start + *d,
+            Frequency::Minute => start + Duration::minutes(1),
+            Frequency::Hour => start + Duration::hours(1),
+            Frequency::Day => start + Duration::days(1),
+            Frequency::Week => start + Duration::weeks(1),
+            Frequency::Month => {
+                let (year, month) = if start.month() == 12 {
+                    (start.year() + 1, 1)
+                } else {
+                    (start.year(), start.month() + 1)
+                };
+                let date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+                Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+            }
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Frequency::Fixed(d) = self {
+            if d.num_milliseconds() <= 0 {
+                return Err(TelemetryError::InvalidParameter(
+                    "Fixed frequency must be a positive duration".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn floor_fixed(t: DateTime<Utc>, d: Duration) -> DateTime<Utc> {
+    let step = d.num_milliseconds();
+    let millis = t.timestamp_millis();
+    let floored = millis - millis.rem_euclid(step);
+    Utc.timestamp_millis_opt(floored).unwrap()
+}
+
+/// Aggregation applied to the values falling in each bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregation {
+    Mean,
+    Sum,
+    Min,
+    Max,
+    /// Earliest observation in the bucket
+    First,
+    /// Latest observation in the bucket
+    Last,
+    /// Number of observations in the bucket
+    Count,
+    /// Quantile in [0, 1], linearly interpolated
+    Quantile(f64),
+}
+
+impl Aggregation {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Aggregation::Quantile(q) = self {
+            if !(0.0..=1.0).contains(q) {
+                return Err(TelemetryError::InvalidParameter(
+                    "Quantile must be between 0 and 1".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Aggregate the values of one bucket, given in timestamp order.
+    ///
+    /// Empty buckets yield `NaN`, except `Sum` and `Count` which yield 0.
+    pub(crate) fn apply(&self, values: &[f64]) -> f64 {
+        match self {
+            Aggregation::Sum => values.iter().sum(),
+            Aggregation::Count => values.len() as f64,
+            _ if values.is_empty() => f64::NAN,
+            Aggregation::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            Aggregation::Min => values.iter().cloned().fold(f64::INFINITY, f64::min),
+            Aggregation::Max => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            Aggregation::First => values[0],
+            Aggregation::Last => values[values.len() - 1],
+            Aggregation::Quantile(q) => {
+                let mut sorted = values.to_vec();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                quantile_sorted(&sorted, *q)
+            }
+        }
+    }
+}
+
+impl TimeSeries {
+    /// Resample onto a regular grid of `freq` buckets.
+    ///
+    /// Each output point is labelled with the start of its bucket. Buckets run
+    /// contiguously from the earliest to the latest timestamp, so upsampling
+    /// leaves `NaN` in buckets without observations. `NaN` inputs are ignored.
+    pub fn resample(&self, freq: Frequency, agg: Aggregation) -> Result<TimeSeries> {
+        freq.validate()?;
+        agg.validate()?;
+
+        let timestamps = self.timestamps.as_ref().ok_or_else(|| {
+            TelemetryError::InvalidData("Resampling requires timestamps".to_string())
+        })?;
+
+        if self.is_empty() {
+            return Err(TelemetryError::InsufficientData(
+                "Cannot resample an empty series".to_string(),
+            ));
+        }
+
+        let mut points: Vec<(DateTime<Utc>, f64)> = timestamps
+            .iter()
+            .cloned()
+            .zip(self.values.iter().cloned())
+            .collect();
+        points.sort_by_key(|&(t, _)| t);
+
+        let end = freq.floor(points[points.len() - 1].0);
+        let mut bucket_start = freq.floor(points[0].0);
+        let mut out_values = Vec::new();
+        let mut out_timestamps = Vec::new();
+        let mut idx = 0;
+
+        while bucket_start <= end {
+            let bucket_end = freq.next(bucket_start);
+            let mut bucket = Vec::new();
+            while idx < points.len() && points[idx].0 < bucket_end {
+                if !points[idx].1.is_nan() {
+                    bucket.push(points[idx].1);
+                }
+                idx += 1;
+            }
+
+            out_values.push(agg.apply(&bucket));
+            out_timestamps.push(bucket_start);
+            bucket_start = bucket_end;
+        }
+
+        Ok(TimeSeries {
+            values: out_values,
+            timestamps: Some(out_timestamps),
+            name: self.name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 4, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_downsample_hourly_mean() {
+        let ts = TimeSeries::with_timestamps(
+            vec![1.0, 3.0, 10.0, 5.0],
+            vec![at(0, 5), at(0, 40), at(1, 10), at(3, 0)],
+        )
+        .unwrap();
+
+        let hourly = ts.resample(Frequency::Hour, Aggregation::Mean).unwrap();
+        assert_eq!(hourly.len(), 4);
+        assert_eq!(hourly.values[0], 2.0);
+        assert_eq!(hourly.values[1], 10.0);
+        assert!(hourly.values[2].is_nan());
+        assert_eq!(hourly.values[3], 5.0);
+        assert_eq!(hourly.timestamps.unwrap()[1], at(1, 0));
+
+        let counts = ts.resample(Frequency::Hour, Aggregation::Count).unwrap();
+        assert_eq!(counts.values, vec![2.0, 1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_calendar_buckets() {
+        // 2024-03-06 is a Wednesday; its ISO week starts on Monday 2024-03-04
+        let wed = Utc.with_ymd_and_hms(2024, 3, 6, 13, 0, 0).unwrap();
+        assert_eq!(Frequency::Week.floor(wed), at(0, 0));
+
+        let dec = Utc.with_ymd_and_hms(2023, 12, 15, 8, 0, 0).unwrap();
+        let month = Frequency::Month.floor(dec);
+        assert_eq!(
+            Frequency::Month.next(month),
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resample_requires_timestamps() {
+        let ts = TimeSeries::new(vec![1.0, 2.0]);
+        assert!(ts.resample(Frequency::Day, Aggregation::Sum).is_err());
+    }
+}
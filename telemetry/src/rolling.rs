@@ -0,0 +1,703 @@
+//! Rolling windows: an O(n) streaming engine plus duration-based windows
+//!
+//! [`RollingAccumulator`] keeps the mean, variance, minimum and maximum of a
+//! sliding window up to date in amortised O(1) per observation (running sums,
+//! Welford updates and monotonic deques). Rolling quantiles use a Fenwick tree
+//! over value ranks, so every window costs O(log n) regardless of its width.
+//!
+//! Unlike the count-based windows of [`TimeSeries::moving_average`] and
+//! [`FeatureExtractor::rolling_statistics`], a [`TimeWindow`] covers a
+//! wall-clock duration around each observation, and the output has one value
+//! per input observation, aligned with the original timestamps. Missing values
+//! inside a time window are ignored; windows with fewer than `min_periods`
+//! observations yield `NaN`.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::features::{FeatureExtractor, RollingStats};
+use crate::resample::Aggregation;
+use crate::{Result, TelemetryError, TimeSeries};
+
+/// Incremental statistics over a sliding window of observations
+///
+/// Observations enter at the back with [`push`](Self::push) and leave from
+/// the front with [`pop`](Self::pop), both in amortised O(1). Missing (`NaN`)
+/// values occupy a slot in the window but are excluded from the statistics.
+/// Infinite values are counted apart from the running sums, so the window
+/// recovers once they leave: while present they make the sum and mean
+/// infinite (`NaN` if both signs occur) and the variance `NaN`. The mean and
+/// variance are recomputed from the window contents once per window length,
+/// so rounding errors from removals do not accumulate.
+#[derive(Debug, Clone, Default)]
+pub struct RollingAccumulator {
+    // Sequence numbers of the oldest slot and of the next push
+    front: usize,
+    back: usize,
+    valid: VecDeque<(usize, f64)>,
+    // Infinite observations in `valid`, kept out of `sum`, `mean` and `m2`
+    positive_inf: usize,
+    negative_inf: usize,
+    sum: f64,
+    mean: f64,
+    m2: f64,
+    // Removals since `sum`, `mean` and `m2` were recomputed from the window
+    stale: usize,
+    mins: VecDeque<(usize, f64)>,
+    maxs: VecDeque<(usize, f64)>,
+}
+
+impl RollingAccumulator {
+    /// Create an empty window
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an observation at the back of the window
+    pub fn push(&mut self, value: f64) {
+        let seq = self.back;
+        self.back += 1;
+        if value.is_nan() {
+            return;
+        }
+
+        self.valid.push_back((seq, value));
+        if value == f64::INFINITY {
+            self.positive_inf += 1;
+        } else if value == f64::NEG_INFINITY {
+            self.negative_inf += 1;
+        } else {
+            self.sum += value;
+            let delta = value - self.mean;
+            self.mean += delta / self.finite() as f64;
+            self.m2 += delta * (value - self.mean);
+        }
+
+        while self.mins.back().is_some_and(|&(_, v)| v >= value) {
+            self.mins.pop_back();
+        }
+        self.mins.push_back((seq, value));
+        while self.maxs.back().is_some_and(|&(_, v)| v <= value) {
+            self.maxs.pop_back();
+        }
+        self.maxs.push_back((seq, value));
+    }
+
+    /// Remove the oldest observation, returning it (`NaN` if it was missing)
+    pub fn pop(&mut self) -> Option<f64> {
+        if self.front == self.back {
+            return None;
+        }
+        let seq = self.front;
+        self.front += 1;
+
+        if self.valid.front().is_none_or(|&(s, _)| s != seq) {
+            return Some(f64::NAN);
+        }
+        let (_, value) = self.valid.pop_front().unwrap();
+
+        if value == f64::INFINITY {
+            self.positive_inf -= 1;
+        } else if value == f64::NEG_INFINITY {
+            self.negative_inf -= 1;
+        } else if self.finite() == 0 {
+            // Start afresh so rounding errors do not outlive the window
+            self.sum = 0.0;
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            self.stale = 0;
+        } else {
+            let before = self.m2;
+            self.sum -= value;
+            let delta = value - self.mean;
+            self.mean -= delta / self.finite() as f64;
+            self.m2 = (self.m2 - delta * (value - self.mean)).max(0.0);
+
+            // Removal updates cancel catastrophically when an outlier leaves
+            // and drift slowly otherwise, so recompute exactly after a large
+            // drop and once per window length (amortised O(1))
+            self.stale += 1;
+            if self.stale >= self.finite() || self.m2 < before * 1e-6 {
+                self.recompute();
+            }
+        }
+
+        if self.mins.front().is_some_and(|&(s, _)| s == seq) {
+            self.mins.pop_front();
+        }
+        if self.maxs.front().is_some_and(|&(s, _)| s == seq) {
+            self.maxs.pop_front();
+        }
+        Some(value)
+    }
+
+    /// Two-pass sum, mean and sum of squared deviations of the finite
+    /// observations in the window
+    fn recompute(&mut self) {
+        let finite = || self.valid.iter().map(|&(_, v)| v).filter(|v| v.is_finite());
+        let sum: f64 = finite().sum();
+        let mean = sum / self.finite() as f64;
+        self.m2 = finite().map(|v| (v - mean).powi(2)).sum();
+        self.sum = sum;
+        self.mean = mean;
+        self.stale = 0;
+    }
+
+    /// Number of finite observations
+    fn finite(&self) -> usize {
+        self.valid.len() - self.positive_inf - self.negative_inf
+    }
+
+    /// Sum of the window if it contains infinite observations
+    fn infinite_sum(&self) -> Option<f64> {
+        match (self.positive_inf > 0, self.negative_inf > 0) {
+            (true, true) => Some(f64::NAN),
+            (true, false) => Some(f64::INFINITY),
+            (false, true) => Some(f64::NEG_INFINITY),
+            (false, false) => None,
+        }
+    }
+
+    /// Number of slots in the window, including missing values
+    pub fn len(&self) -> usize {
+        self.back - self.front
+    }
+
+    /// Check if the window has no slots
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of non-missing observations
+    pub fn count(&self) -> usize {
+        self.valid.len()
+    }
+
+    /// Number of missing observations
+    pub fn missing(&self) -> usize {
+        self.len() - self.count()
+    }
+
+    /// Sum of the non-missing observations (0 when there are none)
+    pub fn sum(&self) -> f64 {
+        self.infinite_sum().unwrap_or(self.sum)
+    }
+
+    /// Mean of the non-missing observations
+    pub fn mean(&self) -> f64 {
+        if self.valid.is_empty() {
+            return f64::NAN;
+        }
+        self.infinite_sum().unwrap_or(self.sum / self.count() as f64)
+    }
+
+    /// Population variance of the non-missing observations
+    pub fn variance(&self) -> f64 {
+        if self.valid.is_empty() || self.infinite_sum().is_some() {
+            return f64::NAN;
+        }
+        self.m2 / self.count() as f64
+    }
+
+    /// Population standard deviation of the non-missing observations
+    pub fn std(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Smallest non-missing observation
+    pub fn min(&self) -> f64 {
+        self.mins.front().map_or(f64::NAN, |&(_, v)| v)
+    }
+
+    /// Largest non-missing observation
+    pub fn max(&self) -> f64 {
+        self.maxs.front().map_or(f64::NAN, |&(_, v)| v)
+    }
+
+    /// Oldest non-missing observation
+    pub fn first(&self) -> f64 {
+        self.valid.front().map_or(f64::NAN, |&(_, v)| v)
+    }
+
+    /// Newest non-missing observation
+    pub fn last(&self) -> f64 {
+        self.valid.back().map_or(f64::NAN, |&(_, v)| v)
+    }
+}
+
+/// Order statistics over a changing subset of a fixed set of values
+///
+/// A Fenwick tree counts the members by rank, giving insertion, removal and
+/// k-th smallest lookups in O(log n).
+#[derive(Debug, Clone)]
+struct RankTree {
+    sorted: Vec<f64>,
+    // Rank of each input position; `None` for missing values
+    ranks: Vec<Option<usize>>,
+    tree: Vec<usize>,
+    count: usize,
+}
+
+impl RankTree {
+    fn new(values: &[f64]) -> Self {
+        let mut order: Vec<usize> = (0..values.len()).filter(|&i| !values[i].is_nan()).collect();
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+        let mut ranks = vec![None; values.len()];
+        for (rank, &i) in order.iter().enumerate() {
+            ranks[i] = Some(rank);
+        }
+
+        Self {
+            sorted: order.iter().map(|&i| values[i]).collect(),
+            ranks,
+            tree: vec![0; order.len() + 1],
+            count: 0,
+        }
+    }
+
+    fn update(&mut self, position: usize, insert: bool) {
+        let Some(rank) = self.ranks[position] else { return };
+        let mut k = rank + 1;
+        while k < self.tree.len() {
+            if insert {
+                self.tree[k] += 1;
+            } else {
+                self.tree[k] -= 1;
+            }
+            k += k & k.wrapping_neg();
+        }
+        if insert {
+            self.count += 1;
+        } else {
+            self.count -= 1;
+        }
+    }
+
+    /// The k-th smallest member (0-based)
+    fn kth(&self, k: usize) -> f64 {
+        let mut pos = 0;
+        let mut remaining = k + 1;
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+        while step > 0 {
+            if pos + step < self.tree.len() && self.tree[pos + step] < remaining {
+                pos += step;
+                remaining -= self.tree[pos];
+            }
+            step >>= 1;
+        }
+        self.sorted[pos]
+    }
+
+    /// Linearly interpolated quantile, as [`Aggregation::Quantile`]
+    fn quantile(&self, q: f64) -> f64 {
+        if self.count == 0 {
+            return f64::NAN;
+        }
+        let pos = q * (self.count - 1) as f64;
+        let (lower, upper) = (pos.floor() as usize, pos.ceil() as usize);
+        let low = self.kth(lower);
+        low + (self.kth(upper) - low) * (pos - lower as f64)
+    }
+}
+
+/// One pass over windows `lo..hi` of a slice whose bounds never move backwards
+pub(crate) struct Sweep<'a> {
+    values: &'a [f64],
+    acc: RollingAccumulator,
+    ranks: Option<RankTree>,
+    lo: usize,
+    hi: usize,
+}
+
+impl<'a> Sweep<'a> {
+    /// `ranked` enables quantiles at the cost of an O(n log n) setup
+    pub(crate) fn new(values: &'a [f64], ranked: bool) -> Self {
+        Self {
+            values,
+            acc: RollingAccumulator::new(),
+            ranks: ranked.then(|| RankTree::new(values)),
+            lo: 0,
+            hi: 0,
+        }
+    }
+
+    /// Move the window to `lo..hi`
+    pub(crate) fn advance(&mut self, lo: usize, hi: usize) -> &RollingAccumulator {
+        debug_assert!(lo >= self.lo && hi >= self.hi && lo <= hi);
+        while self.hi < hi {
+            self.acc.push(self.values[self.hi]);
+            if let Some(ranks) = &mut self.ranks {
+                ranks.update(self.hi, true);
+            }
+            self.hi += 1;
+        }
+        while self.lo < lo {
+            self.acc.pop();
+            if let Some(ranks) = &mut self.ranks {
+                ranks.update(self.lo, false);
+            }
+            self.lo += 1;
+        }
+        &self.acc
+    }
+
+    pub(crate) fn quantile(&self, q: f64) -> f64 {
+        self.ranks.as_ref().map_or(f64::NAN, |ranks| ranks.quantile(q))
+    }
+
+    /// Current window aggregated like [`Aggregation::apply`]
+    pub(crate) fn aggregate(&self, agg: Aggregation) -> f64 {
+        let acc = &self.acc;
+        match agg {
+            Aggregation::Sum => acc.sum(),
+            Aggregation::Count => acc.count() as f64,
+            Aggregation::Mean => acc.mean(),
+            Aggregation::Min => acc.min(),
+            Aggregation::Max => acc.max(),
+            Aggregation::First => acc.first(),
+            Aggregation::Last => acc.last(),
+            Aggregation::Quantile(q) => self.quantile(q),
+        }
+    }
+}
+
+/// Position of the window relative to each observation at time `t`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowClosure {
+    /// Trailing window `(t - d, t]`, closed on the right ("last 1h")
+    Right,
+    /// Leading window `[t, t + d)`, closed on the left
+    Left,
+    /// Centered window `[t - d/2, t + d/2]`
+    Center,
+}
+
+/// Duration-based window specification
+#[derive(Debug, Clone, Copy)]
+pub struct TimeWindow {
+    duration: Duration,
+    closure: WindowClosure,
+    min_periods: usize,
+}
+
+impl TimeWindow {
+    /// Trailing window of `duration` requiring one observation
+    pub fn new(duration: Duration) -> Result<Self> {
+        if duration <= Duration::zero() {
+            return Err(TelemetryError::InvalidParameter(
+                "Window duration must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            duration,
+            closure: WindowClosure::Right,
+            min_periods: 1,
+        })
+    }
+
+    /// Set the window position relative to each observation
+    pub fn with_closure(mut self, closure: WindowClosure) -> Self {
+        self.closure = closure;
+        self
+    }
+
+    /// Minimum number of non-missing observations for a result
+    pub fn with_min_periods(mut self, min_periods: usize) -> Result<Self> {
+        if min_periods == 0 {
+            return Err(TelemetryError::InvalidParameter(
+                "Minimum periods must be at least 1".to_string(),
+            ));
+        }
+        self.min_periods = min_periods;
+        Ok(self)
+    }
+
+    /// Index ranges `lo..hi` of the window around every observation
+    pub(crate) fn bounds(&self, timestamps: &[DateTime<Utc>]) -> Vec<(usize, usize)> {
+        let d = self.duration;
+        timestamps
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| match self.closure {
+                WindowClosure::Right => (timestamps.partition_point(|&x| x <= t - d), i + 1),
+                WindowClosure::Left => (i, timestamps.partition_point(|&x| x < t + d)),
+                WindowClosure::Center => {
+                    let half = d / 2;
+                    (
+                        timestamps.partition_point(|&x| x < t - half),
+                        timestamps.partition_point(|&x| x <= t + half),
+                    )
+                }
+            })
+            .collect()
+    }
+
+    /// Visit every window in one sweep; `f` receives `None` when the
+    /// window has fewer than `min_periods` observations
+    fn sweep<F: FnMut(Option<&Sweep>)>(&self, ts: &TimeSeries, ranked: bool, mut f: F) -> Result<()> {
+        ts.validate_timestamps()?;
+        let timestamps = ts.timestamps.as_ref().unwrap();
+
+        let mut sweep = Sweep::new(&ts.values, ranked);
+        for (lo, hi) in self.bounds(timestamps) {
+            let enough = sweep.advance(lo, hi).count() >= self.min_periods;
+            f(enough.then_some(&sweep));
+        }
+        Ok(())
+    }
+}
+
+impl TimeSeries {
+    /// Aggregate over a duration-based window around every observation,
+    /// returning a series aligned with the original timestamps
+    ///
+    /// Requires strictly increasing timestamps.
+    pub fn rolling_time(&self, window: &TimeWindow, agg: Aggregation) -> Result<TimeSeries> {
+        agg.validate()?;
+        let ranked = matches!(agg, Aggregation::Quantile(_));
+        let mut values = Vec::with_capacity(self.len());
+        window.sweep(self, ranked, |w| values.push(w.map_or(f64::NAN, |w| w.aggregate(agg))))?;
+        Ok(TimeSeries {
+            values,
+            timestamps: self.timestamps.clone(),
+            name: self.name.clone(),
+        })
+    }
+
+    /// Mean over a duration-based window, aligned with the original timestamps
+    pub fn moving_average_time(&self, window: &TimeWindow) -> Result<TimeSeries> {
+        self.rolling_time(window, Aggregation::Mean)
+    }
+
+    /// Quantile `q` of every window of `window` observations, in O(n log n)
+    ///
+    /// Like [`TimeSeries::moving_average`], the result has one value per full
+    /// window and windows containing a missing (`NaN`) value yield `NaN`.
+    pub fn rolling_quantile(&self, window: usize, q: f64) -> Result<Vec<f64>> {
+        Aggregation::Quantile(q).validate()?;
+        if window == 0 {
+            return Err(TelemetryError::InvalidParameter(
+                "Window size must be greater than 0".to_string(),
+            ));
+        }
+
+        if window > self.values.len() {
+            return Err(TelemetryError::InsufficientData(
+                format!("Window size {} is larger than data length {}", window, self.values.len()),
+            ));
+        }
+
+        let mut sweep = Sweep::new(&self.values, true);
+        Ok((0..=(self.values.len() - window))
+            .map(|i| {
+                if sweep.advance(i, i + window).missing() > 0 {
+                    f64::NAN
+                } else {
+                    sweep.quantile(q)
+                }
+            })
+            .collect())
+    }
+
+    /// Median of every window of `window` observations
+    pub fn rolling_median(&self, window: usize) -> Result<Vec<f64>> {
+        self.rolling_quantile(window, 0.5)
+    }
+}
+
+impl FeatureExtractor {
+    /// Rolling mean, standard deviation, min and max over a duration-based
+    /// window, with one value per observation
+    pub fn rolling_statistics_time(ts: &TimeSeries, window: &TimeWindow) -> Result<RollingStats> {
+        let mut stats = RollingStats {
+            means: Vec::with_capacity(ts.len()),
+            stds: Vec::with_capacity(ts.len()),
+            mins: Vec::with_capacity(ts.len()),
+            maxs: Vec::with_capacity(ts.len()),
+        };
+
+        window.sweep(ts, false, |w| {
+            let acc = w.map(|w| &w.acc);
+            stats.means.push(acc.map_or(f64::NAN, |a| a.mean()));
+            stats.stds.push(acc.map_or(f64::NAN, |a| a.std()));
+            stats.mins.push(acc.map_or(f64::NAN, |a| a.min()));
+            stats.maxs.push(acc.map_or(f64::NAN, |a| a.max()));
+        })?;
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn irregular() -> TimeSeries {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let minutes = [0, 10, 20, 50, 55, 120];
+        let timestamps = minutes.iter().map(|m| start + Duration::minutes(*m)).collect();
+        TimeSeries::with_timestamps(vec![1.0, 2.0, 3.0, 4.0, f64::NAN, 6.0], timestamps).unwrap()
+    }
+
+    #[test]
+    fn test_trailing_window() {
+        let ts = irregular();
+        let window = TimeWindow::new(Duration::minutes(30)).unwrap();
+        let mean = ts.moving_average_time(&window).unwrap();
+
+        assert_eq!(mean.len(), ts.len());
+        assert_eq!(mean.timestamps, ts.timestamps);
+        // (20, 50]: only minute 50; (25, 55]: minute 50 plus a missing value
+        assert_eq!(mean.values[2], 2.0);
+        assert_eq!(mean.values[3], 4.0);
+        assert_eq!(mean.values[4], 4.0);
+
+        let strict = window.with_min_periods(2).unwrap();
+        let sum = ts.rolling_time(&strict, Aggregation::Sum).unwrap();
+        assert!(sum.values[0].is_nan());
+        assert_eq!(sum.values[1], 3.0);
+        assert!(sum.values[5].is_nan());
+    }
+
+    #[test]
+    fn test_leading_and_centered_windows() {
+        let ts = irregular();
+        let leading = TimeWindow::new(Duration::minutes(20)).unwrap().with_closure(WindowClosure::Left);
+        let max = ts.rolling_time(&leading, Aggregation::Max).unwrap();
+        assert_eq!(max.values[0], 2.0);
+
+        let centered = TimeWindow::new(Duration::minutes(20)).unwrap().with_closure(WindowClosure::Center);
+        let stats = FeatureExtractor::rolling_statistics_time(&ts, &centered).unwrap();
+        assert_eq!(stats.means[1], 2.0);
+        assert_eq!(stats.mins[1], 1.0);
+        assert_eq!(stats.maxs[1], 3.0);
+
+        assert!(TimeWindow::new(Duration::zero()).is_err());
+        assert!(TimeSeries::new(vec![1.0]).moving_average_time(&leading).is_err());
+    }
+
+    fn noisy(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| if i % 17 == 5 { f64::NAN } else { ((i * 7919) % 101) as f64 - 50.0 })
+            .collect()
+    }
+
+    #[test]
+    fn test_accumulator_matches_direct_computation() {
+        let values = noisy(300);
+        let window = 25;
+        let mut acc = RollingAccumulator::new();
+
+        for (i, &v) in values.iter().enumerate() {
+            acc.push(v);
+            if acc.len() > window {
+                acc.pop();
+            }
+            let present: Vec<f64> = values[i + 1 - acc.len()..=i].iter().copied().filter(|v| !v.is_nan()).collect();
+            let mean = present.iter().sum::<f64>() / present.len() as f64;
+            let variance = present.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / present.len() as f64;
+
+            assert_eq!(acc.count(), present.len());
+            assert!((acc.mean() - mean).abs() < 1e-9);
+            assert!((acc.variance() - variance).abs() < 1e-9);
+            assert_eq!(acc.min(), Aggregation::Min.apply(&present));
+            assert_eq!(acc.max(), Aggregation::Max.apply(&present));
+            assert_eq!(acc.first(), present[0]);
+        }
+
+        while acc.pop().is_some() {}
+        assert!(acc.is_empty());
+        assert!(acc.mean().is_nan());
+    }
+
+    #[test]
+    fn test_accumulator_stays_exact_after_outlier() {
+        // A 20 °C series with one sensor glitch, and a series at level 1e9
+        for (level, glitch) in [(20.0, 1e6), (1e9, 0.0)] {
+            let values: Vec<f64> = (0..20_000)
+                .map(|i| level + ((i * 7919) % 101) as f64 / 100.0 + if i == 100 { glitch } else { 0.0 })
+                .collect();
+            let window = 24;
+            let mut acc = RollingAccumulator::new();
+            let mut worst: f64 = 0.0;
+
+            for (i, &v) in values.iter().enumerate() {
+                acc.push(v);
+                if acc.len() > window {
+                    acc.pop();
+                }
+                if i < 100 + window {
+                    continue;
+                }
+                let present = &values[i + 1 - window..=i];
+                let mean = present.iter().sum::<f64>() / window as f64;
+                let std = (present.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / window as f64).sqrt();
+                worst = worst.max((acc.std() - std).abs() / std);
+            }
+            assert!(worst < 1e-6, "relative std error {} at level {}", worst, level);
+        }
+    }
+
+    #[test]
+    fn test_accumulator_recovers_after_infinite_value() {
+        let mut values: Vec<f64> = (0..200).map(|i| ((i * 37) % 11) as f64 / 2.0).collect();
+        values[10] = f64::INFINITY;
+        let window = 50;
+        let ts = TimeSeries::new(values.clone());
+        let averages = ts.moving_average(window).unwrap();
+        let stats = FeatureExtractor::rolling_statistics(&ts, window).unwrap();
+
+        for (i, present) in values.windows(window).enumerate() {
+            if i <= 10 {
+                assert_eq!(averages[i], f64::INFINITY);
+                assert!(stats.stds[i].is_nan());
+                continue;
+            }
+            let mean = present.iter().sum::<f64>() / window as f64;
+            let std = (present.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / window as f64).sqrt();
+            assert!((averages[i] - mean).abs() < 1e-9, "mean {} != {} at {}", averages[i], mean, i);
+            assert!((stats.means[i] - mean).abs() < 1e-9);
+            assert!((stats.stds[i] - std).abs() < 1e-9);
+        }
+
+        let mut acc = RollingAccumulator::new();
+        acc.push(f64::INFINITY);
+        acc.push(f64::NEG_INFINITY);
+        acc.push(1.0);
+        assert!(acc.mean().is_nan());
+        assert_eq!((acc.min(), acc.count()), (f64::NEG_INFINITY, 3));
+        acc.pop();
+        assert_eq!(acc.sum(), f64::NEG_INFINITY);
+        acc.pop();
+        assert_eq!((acc.mean(), acc.variance()), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_rolling_quantiles() {
+        let ts = TimeSeries::new(vec![5.0, 1.0, 4.0, 2.0, 3.0, 3.0]);
+        assert_eq!(ts.rolling_median(3).unwrap(), vec![4.0, 2.0, 3.0, 3.0]);
+        assert_eq!(ts.rolling_quantile(2, 1.0).unwrap(), vec![5.0, 4.0, 4.0, 3.0, 3.0]);
+        assert!(ts.rolling_quantile(2, 1.5).is_err());
+        assert!(ts.rolling_median(7).is_err());
+
+        let values = noisy(200);
+        let quartiles = TimeSeries::new(values.clone()).rolling_quantile(12, 0.25).unwrap();
+        for (i, q) in quartiles.iter().enumerate() {
+            let window = &values[i..i + 12];
+            if window.iter().any(|v| v.is_nan()) {
+                assert!(q.is_nan());
+            } else {
+                assert_eq!(*q, Aggregation::Quantile(0.25).apply(window));
+            }
+        }
+
+        let ts = irregular();
+        let window = TimeWindow::new(Duration::minutes(30)).unwrap();
+        let median = ts.rolling_time(&window, Aggregation::Quantile(0.5)).unwrap();
+        assert_eq!(median.values[1], 1.5);
+        assert_eq!(median.values[4], 4.0);
+    }
+}
@@ -0,0 +1,318 @@
+//! Spectral analysis and automatic period detection (requires the `fft` feature)
+//!
+//! Power spectra are estimated on the linearly detrended series. Candidate
+//! periods come from peaks of a zero-padded periodogram and are kept only if
+//! the autocorrelation at that lag is a significant local maximum, which
+//! rejects harmonics and spectral leakage. Accepted cycles are removed
+//! before weaker peaks are checked.
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+use crate::{Result, TelemetryError, TimeSeries};
+
+/// Power spectral density estimate
+#[derive(Debug, Clone)]
+pub struct Periodogram {
+    /// Frequencies in cycles per observation, in (0, 0.5]
+    pub frequencies: Vec<f64>,
+    /// One-sided power spectral density at each frequency
+    pub power: Vec<f64>,
+}
+
+impl Periodogram {
+    /// Period (in observations) of each frequency
+    pub fn periods(&self) -> Vec<f64> {
+        self.frequencies.iter().map(|f| 1.0 / f).collect()
+    }
+
+    /// Frequency with the highest power
+    pub fn peak_frequency(&self) -> Option<f64> {
+        self.power
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(k, _)| self.frequencies[k])
+    }
+}
+
+/// A candidate seasonal period
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandidatePeriod {
+    /// Period in observations
+    pub period: usize,
+    /// Share of the total spectral power in the peak, in [0, 1]
+    pub power: f64,
+    /// Autocorrelation at lag `period`, once stronger cycles are removed
+    pub autocorrelation: f64,
+    /// Ranking score: `power * autocorrelation`
+    pub strength: f64,
+}
+
+/// DFT of `x` zero-padded to `len`
+fn spectrum(x: &[f64], len: usize) -> Vec<Complex<f64>> {
+    let mut buffer: Vec<Complex<f64>> = x.iter().map(|&v| Complex::new(v, 0.0)).collect();
+    buffer.resize(len, Complex::new(0.0, 0.0));
+    FftPlanner::new().plan_fft_forward(len).process(&mut buffer);
+    buffer
+}
+
+/// Squared magnitudes of the DFT of `x`, zero-padded to `len`
+pub(crate) fn fft_power(x: &[f64], len: usize) -> Vec<f64> {
+    spectrum(x, len).iter().map(|c| c.norm_sqr()).collect()
+}
+
+/// Autocorrelations at lags `0..=max_lag` of a demeaned series, computed in
+/// O(n log n) as the inverse transform of its power spectrum
+pub(crate) fn autocorrelation_fft(x: &[f64], max_lag: usize) -> Vec<f64> {
+    let len = (2 * x.len()).next_power_of_two();
+    let mut buffer: Vec<Complex<f64>> = fft_power(x, len).into_iter().map(|p| Complex::new(p, 0.0)).collect();
+    FftPlanner::new().plan_fft_inverse(len).process(&mut buffer);
+
+    let c0 = buffer[0].re;
+    (0..=max_lag).map(|k| if c0 > 0.0 { buffer[k].re / c0 } else { f64::NAN }).collect()
+}
+
+/// Cross-covariances `sum_t x[t] * y[t + k] / n` of two demeaned series of
+/// equal length for lags `k = -max_lag..=max_lag`, in O(n log n)
+pub(crate) fn cross_covariance_fft(x: &[f64], y: &[f64], max_lag: usize) -> Vec<f64> {
+    let n = x.len();
+    let len = (2 * n).next_power_of_two();
+    let mut buffer: Vec<Complex<f64>> = spectrum(x, len)
+        .into_iter()
+        .zip(spectrum(y, len))
+        .map(|(a, b)| a.conj() * b)
+        .collect();
+    FftPlanner::new().plan_fft_inverse(len).process(&mut buffer);
+
+    let scale = (len * n) as f64;
+    (-(max_lag as isize)..=max_lag as isize)
+        .map(|k| buffer[k.rem_euclid(len as isize) as usize].re / scale)
+        .collect()
+}
+
+/// Values with the least-squares line removed
+fn detrended(ts: &TimeSeries) -> Result<Vec<f64>> {
+    if ts.len() < 8 {
+        return Err(TelemetryError::InsufficientData(
+            "Need at least 8 data points for spectral analysis".to_string(),
+        ));
+    }
+    if ts.values.iter().any(|v| !v.is_finite()) {
+        return Err(TelemetryError::InvalidData(
+            "Spectral analysis requires finite values; fill missing values first".to_string(),
+        ));
+    }
+
+    let n = ts.len() as f64;
+    let t_mean = (n - 1.0) / 2.0;
+    let y_mean = ts.values.iter().sum::<f64>() / n;
+    let sxx: f64 = (0..ts.len()).map(|t| (t as f64 - t_mean).powi(2)).sum();
+    let sxy: f64 = ts.values.iter().enumerate().map(|(t, y)| (t as f64 - t_mean) * (y - y_mean)).sum();
+    let slope = sxy / sxx;
+
+    Ok(ts
+        .values
+        .iter()
+        .enumerate()
+        .map(|(t, y)| y - y_mean - slope * (t as f64 - t_mean))
+        .collect())
+}
+
+/// One-sided densities for frequencies `k / len`, `k = 1..=len/2`, given
+/// squared DFT magnitudes and the window's sum of squares
+fn one_sided(power: &[f64], len: usize, scale: f64) -> Periodogram {
+    let frequencies = (1..=len / 2).map(|k| k as f64 / len as f64).collect();
+    let power = (1..=len / 2)
+        .map(|k| {
+            let double = if 2 * k == len { 1.0 } else { 2.0 };
+            double * power[k] / scale
+        })
+        .collect();
+    Periodogram { frequencies, power }
+}
+
+/// Periodogram of the detrended series
+///
+/// Densities are scaled so that their sum times `1/n` equals the variance.
+pub fn periodogram(ts: &TimeSeries) -> Result<Periodogram> {
+    let x = detrended(ts)?;
+    let n = x.len();
+    Ok(one_sided(&fft_power(&x, n), n, n as f64))
+}
+
+/// Welch's power spectral density: the average periodogram of Hann-windowed
+/// segments of `segment` observations overlapping by half
+pub fn welch(ts: &TimeSeries, segment: usize) -> Result<Periodogram> {
+    if segment < 8 || segment > ts.len() {
+        return Err(TelemetryError::InvalidParameter(
+            "Segment length must be between 8 and the series length".to_string(),
+        ));
+    }
+    let x = detrended(ts)?;
+
+    let window: Vec<f64> = (0..segment)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f64::consts::PI * i as f64 / segment as f64).cos())
+        .collect();
+    let scale: f64 = window.iter().map(|w| w * w).sum();
+
+    let step = (segment / 2).max(1);
+    let mut total = vec![0.0; segment];
+    let mut count = 0;
+    for start in (0..=x.len() - segment).step_by(step) {
+        let tapered: Vec<f64> = x[start..start + segment].iter().zip(&window).map(|(v, w)| v * w).collect();
+        total.iter_mut().zip(fft_power(&tapered, segment)).for_each(|(t, p)| *t += p);
+        count += 1;
+    }
+    total.iter_mut().for_each(|t| *t /= count as f64);
+
+    Ok(one_sided(&total, segment, scale))
+}
+
+/// Ranked candidate periods, strongest first (at most `max_candidates`)
+///
+/// Periodogram peaks are examined from the most powerful down. Each is
+/// rounded to the lag with the highest nearby autocorrelation and kept only
+/// if that autocorrelation is a local maximum above the 95% white-noise band
+/// `1.96 / sqrt(n)`; the autocorrelation is computed after removing the
+/// cycles already accepted. Periods range from 2 to half the series length.
+pub fn detect_periods(ts: &TimeSeries, max_candidates: usize) -> Result<Vec<CandidatePeriod>> {
+    let x = detrended(ts)?;
+    let n = x.len();
+    let max_period = n / 2;
+
+    // Zero padding refines the frequency grid for periods not dividing n
+    let len = (4 * n).next_power_of_two();
+    let spectrum = fft_power(&x, len);
+    let half = &spectrum[..=len / 2];
+    let total: f64 = half[1..].iter().sum();
+    if total <= 0.0 {
+        return Ok(Vec::new());
+    }
+
+    let band = 1.96 / (n as f64).sqrt();
+
+    let mut peaks: Vec<usize> = (1..half.len() - 1)
+        .filter(|&k| half[k] > half[k - 1] && half[k] >= half[k + 1])
+        .collect();
+    peaks.sort_by(|&a, &b| half[b].total_cmp(&half[a]));
+
+    // Accepted cycles are removed before checking weaker peaks, so that a
+    // strong cycle does not mask the autocorrelation of the others
+    let mut remaining = x;
+    let mut acf = autocorrelation_fft(&remaining, max_period + 1);
+    let mut candidates: Vec<CandidatePeriod> = Vec::new();
+    for k in peaks {
+        let exact = len as f64 / k as f64;
+        if exact < 1.5 || exact > max_period as f64 + 0.5 {
+            continue;
+        }
+
+        let centre = exact.round() as usize;
+        let lag = (centre.saturating_sub(1).max(2)..=(centre + 1).min(max_period))
+            .max_by(|&a, &b| acf[a].total_cmp(&acf[b]))
+            .unwrap_or(centre);
+        let is_local_max = acf[lag] >= acf[lag - 1] && acf[lag] >= acf[lag + 1];
+        if !is_local_max || acf[lag] <= band || candidates.iter().any(|c| c.period == lag) {
+            continue;
+        }
+
+        // Power of the whole peak, down to the neighbouring troughs
+        let mut lo = k;
+        while lo > 1 && half[lo - 1] < half[lo] {
+            lo -= 1;
+        }
+        let mut hi = k;
+        while hi + 1 < half.len() && half[hi + 1] < half[hi] {
+            hi += 1;
+        }
+        let power = half[lo..=hi].iter().sum::<f64>() / total;
+
+        candidates.push(CandidatePeriod {
+            period: lag,
+            power,
+            autocorrelation: acf[lag],
+            strength: power * acf[lag],
+        });
+        remove_cycle(&mut remaining, lag);
+        acf = autocorrelation_fft(&remaining, max_period + 1);
+    }
+
+    candidates.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+    candidates.truncate(max_candidates);
+    Ok(candidates)
+}
+
+/// Subtract the mean of every phase of a cycle of length `period`
+fn remove_cycle(x: &mut [f64], period: usize) {
+    for phase in 0..period {
+        let count = (x.len() - phase).div_ceil(period);
+        let mean = x[phase..].iter().step_by(period).sum::<f64>() / count as f64;
+        x[phase..].iter_mut().step_by(period).for_each(|v| *v -= mean);
+    }
+}
+
+/// Strongest seasonal period, if any passes the checks of [`detect_periods`]
+pub fn dominant_period(ts: &TimeSeries) -> Result<Option<usize>> {
+    Ok(detect_periods(ts, 1)?.first().map(|c| c.period))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::shocks;
+    use std::f64::consts::PI;
+
+    fn seasonal(n: usize, components: &[(f64, f64)], noise: f64) -> TimeSeries {
+        let e = shocks(n);
+        let values = (0..n)
+            .map(|t| {
+                let cycles: f64 = components.iter().map(|(period, amp)| amp * (2.0 * PI * t as f64 / period).sin()).sum();
+                20.0 + 0.1 * t as f64 + cycles + noise * e[t]
+            })
+            .collect();
+        TimeSeries::new(values)
+    }
+
+    #[test]
+    fn test_periodogram_peak_and_scaling() {
+        let ts = seasonal(240, &[(12.0, 2.0)], 0.0);
+        let pg = periodogram(&ts).unwrap();
+        assert_eq!(pg.frequencies.len(), 120);
+        assert!((pg.peak_frequency().unwrap() - 1.0 / 12.0).abs() < 1e-12);
+
+        // Sine of amplitude 2 has variance 2
+        let variance = pg.power.iter().sum::<f64>() / 240.0;
+        assert!((variance - 2.0).abs() < 0.05);
+
+        let psd = welch(&ts, 48).unwrap();
+        assert!((psd.peak_frequency().unwrap() - 1.0 / 12.0).abs() < 1e-12);
+        assert!(welch(&ts, 4).is_err());
+    }
+
+    #[test]
+    fn test_detect_multiple_periods() {
+        let ts = seasonal(600, &[(7.0, 3.0), (30.0, 2.0)], 0.5);
+        let candidates = detect_periods(&ts, 5).unwrap();
+        let periods: Vec<usize> = candidates.iter().map(|c| c.period).collect();
+
+        assert_eq!(periods[0], 7);
+        assert!(periods.contains(&30));
+        assert!(candidates.iter().all(|c| c.power > 0.0 && c.power <= 1.0));
+
+        // Square waves have strong harmonics at 1/2 and 1/3 of the period
+        let square: Vec<f64> = (0..240).map(|t| if t % 24 < 12 { 1.0 } else { -1.0 }).collect();
+        assert_eq!(dominant_period(&TimeSeries::new(square)).unwrap(), Some(24));
+    }
+
+    #[test]
+    fn test_noise_and_invalid_input() {
+        let noise = TimeSeries::new(shocks(300));
+        let candidates = detect_periods(&noise, 3).unwrap();
+        assert!(candidates.iter().all(|c| c.strength < 0.02));
+
+        assert!(dominant_period(&TimeSeries::new(vec![1.0; 50])).unwrap().is_none());
+        assert!(periodogram(&TimeSeries::new(vec![1.0, 2.0])).is_err());
+        assert!(periodogram(&TimeSeries::new(vec![1.0, f64::NAN, 2.0, 3.0, 1.0, 2.0, 3.0, 4.0])).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+//! Reproducible noise shared by the unit tests
+
+/// Deterministic pseudo-random standard normal shocks
+pub(crate) fn shocks(n: usize) -> Vec<f64> {
+    seeded_shocks(n, 42)
+}
+
+/// Standard normal draws from an independent stream for each `seed`
+pub(crate) fn seeded_shocks(n: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    let mut uniform = move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    };
+    (0..n)
+        .map(|_| {
+            let (u1, u2) = (uniform(), uniform());
+            (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+        })
+        .collect()
+}
@@ -0,0 +1,312 @@
+//! Theta method forecaster
+//!
+//! The series is split into a linear trend line and a "theta line"
+//! `Z = θ·y + (1 - θ)·(a + b·t)` that is extrapolated by simple exponential
+//! smoothing. Forecasts combine the two with weights `1 - 1/θ` and `1/θ`.
+//! The classical method fixes `θ = 2`; the optimised variant estimates `θ`
+//! together with the smoothing parameter. Seasonal series can be adjusted
+//! beforehand with a classical multiplicative decomposition.
+
+use crate::{Result, TelemetryError, TimeSeries};
+use crate::decomposition::{Decomposer, DecompositionType};
+use crate::forecasting::{ForecastResult, Forecaster};
+use crate::models::arima::autocovariance;
+use crate::optim::{from_interval, nelder_mead, to_interval};
+
+/// Range of θ searched by the optimised method
+const THETA_BOUNDS: (f64, f64) = (1.0, 10.0);
+
+/// 90% normal quantile used by the seasonality test
+const SEASONALITY_Z: f64 = 1.645;
+
+#[derive(Debug, Clone)]
+struct ThetaFit {
+    alpha: f64,
+    theta: f64,
+    intercept: f64,
+    slope: f64,
+    level: f64,
+    sigma2: f64,
+    n: usize,
+    /// Seasonal indices for positions `n, n + 1, ...` modulo the period
+    seasonal: Option<Vec<f64>>,
+}
+
+/// Theta method forecaster
+#[derive(Debug, Clone)]
+pub struct Theta {
+    optimize_theta: bool,
+    period: Option<usize>,
+    fit: Option<ThetaFit>,
+}
+
+impl Default for Theta {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Theta {
+    /// Classical Theta method (θ = 2, optimised smoothing parameter)
+    pub fn new() -> Self {
+        Self {
+            optimize_theta: false,
+            period: None,
+            fit: None,
+        }
+    }
+
+    /// Optimised Theta method: θ is estimated with the smoothing parameter
+    pub fn optimized() -> Self {
+        Self {
+            optimize_theta: true,
+            ..Self::new()
+        }
+    }
+
+    /// Seasonally adjust with a multiplicative decomposition of `period`
+    /// when the lag-`period` autocorrelation is significant (90% level)
+    pub fn with_seasonal_period(mut self, period: usize) -> Result<Self> {
+        if period < 2 {
+            return Err(TelemetryError::InvalidParameter(
+                "Seasonal period must be at least 2".to_string(),
+            ));
+        }
+        self.period = Some(period);
+        Ok(self)
+    }
+
+    /// Fitted θ
+    pub fn theta(&self) -> Option<f64> {
+        self.fit.as_ref().map(|f| f.theta)
+    }
+
+    /// Fitted smoothing parameter
+    pub fn alpha(&self) -> Option<f64> {
+        self.fit.as_ref().map(|f| f.alpha)
+    }
+
+    /// Whether the fitted series was seasonally adjusted
+    pub fn seasonally_adjusted(&self) -> bool {
+        self.fit.as_ref().is_some_and(|f| f.seasonal.is_some())
+    }
+
+    /// Seasonal indices when the series shows significant seasonality at
+    /// `period`, aligned so that element `i` applies to positions `n + i`
+    fn seasonal_indices(ts: &TimeSeries, period: usize) -> Result<Option<Vec<f64>>> {
+        let n = ts.len();
+        if n < 2 * period {
+            return Ok(None);
+        }
+
+        let mean = ts.values.iter().sum::<f64>() / n as f64;
+        let centered: Vec<f64> = ts.values.iter().map(|v| v - mean).collect();
+        let acov = autocovariance(&centered, period);
+        if acov[0] <= 0.0 {
+            return Ok(None);
+        }
+        let acf: Vec<f64> = acov.iter().map(|c| c / acov[0]).collect();
+        let bartlett = (1.0 + 2.0 * acf[1..period].iter().map(|r| r * r).sum::<f64>()) / n as f64;
+        if acf[period].abs() <= SEASONALITY_Z * bartlett.sqrt() {
+            return Ok(None);
+        }
+
+        if ts.values.iter().any(|&v| v <= 0.0) {
+            return Err(TelemetryError::InvalidData(
+                "Multiplicative seasonal adjustment requires positive values".to_string(),
+            ));
+        }
+
+        let decomposition = Decomposer::new(DecompositionType::Multiplicative, period)?.decompose(ts)?;
+        Ok(Some((0..period).map(|i| decomposition.seasonal[(n + i) % period]).collect()))
+    }
+}
+
+/// In-sample run of the theta recursions; returns the SSE of the one-step
+/// forecasts and the final SES level of the theta line
+fn theta_recursion(y: &[f64], intercept: f64, slope: f64, theta: f64, alpha: f64, level0: f64) -> (f64, f64) {
+    let weight = 1.0 - 1.0 / theta;
+    let mut level = level0;
+    let mut sse = 0.0;
+    for (t, &value) in y.iter().enumerate() {
+        let trend = intercept + slope * t as f64;
+        let forecast = weight * trend + (1.0 - weight) * level;
+        sse += (value - forecast).powi(2);
+
+        let z = theta * value + (1.0 - theta) * trend;
+        level = alpha * z + (1.0 - alpha) * level;
+    }
+    (sse, level)
+}
+
+impl Forecaster for Theta {
+    fn fit(&mut self, ts: &TimeSeries) -> Result<()> {
+        if ts.len() < 3 {
+            return Err(TelemetryError::InsufficientData(
+                "Theta method needs at least 3 data points".to_string(),
+            ));
+        }
+        if ts.has_missing() {
+            return Err(TelemetryError::InvalidData(
+                "Theta method cannot be fitted on a series with missing values".to_string(),
+            ));
+        }
+
+        let n = ts.len();
+        let seasonal = match self.period {
+            Some(period) => Self::seasonal_indices(ts, period)?,
+            None => None,
+        };
+        let y: Vec<f64> = match &seasonal {
+            Some(indices) => {
+                let period = indices.len();
+                ts.values
+                    .iter()
+                    .enumerate()
+                    // Index for position t is indices[(t - n) mod period]
+                    .map(|(t, v)| v / indices[(t + period - n % period) % period])
+                    .collect()
+            }
+            None => ts.values.clone(),
+        };
+
+        // Linear trend line by least squares on t = 0..n-1
+        let t_mean = (n - 1) as f64 / 2.0;
+        let y_mean = y.iter().sum::<f64>() / n as f64;
+        let sxy: f64 = y.iter().enumerate().map(|(t, v)| (t as f64 - t_mean) * (v - y_mean)).sum();
+        let sxx: f64 = (0..n).map(|t| (t as f64 - t_mean).powi(2)).sum();
+        let slope = sxy / sxx;
+        let intercept = y_mean - slope * t_mean;
+
+        let scale = (y.iter().map(|v| (v - y_mean).powi(2)).sum::<f64>() / n as f64).sqrt().max(1e-8);
+        let unpack = |x: &[f64]| {
+            let alpha = to_interval(x[0], 0.0, 1.0);
+            let theta = if self.optimize_theta {
+                to_interval(x[2], THETA_BOUNDS.0, THETA_BOUNDS.1)
+            } else {
+                2.0
+            };
+            (alpha, y[0] + x[1] * scale, theta)
+        };
+
+        let mut x0 = vec![from_interval(0.3, 0.0, 1.0), 0.0];
+        if self.optimize_theta {
+            x0.push(from_interval(2.0, THETA_BOUNDS.0, THETA_BOUNDS.1));
+        }
+        let objective = |x: &[f64]| {
+            let (alpha, level0, theta) = unpack(x);
+            theta_recursion(&y, intercept, slope, theta, alpha, level0).0
+        };
+        let (best, _) = nelder_mead(objective, &x0, 0.5, 2000, 1e-10);
+        let (alpha, level0, theta) = unpack(&best);
+        let (sse, level) = theta_recursion(&y, intercept, slope, theta, alpha, level0);
+
+        self.fit = Some(ThetaFit {
+            alpha,
+            theta,
+            intercept,
+            slope,
+            level,
+            sigma2: sse / (n - 1) as f64,
+            n,
+            seasonal,
+        });
+        Ok(())
+    }
+
+    fn forecast(&self, steps: usize) -> Result<ForecastResult> {
+        let fit = self.fit.as_ref().ok_or_else(|| {
+            TelemetryError::ModelError("Model must be fitted before forecasting".to_string())
+        })?;
+
+        let weight = 1.0 - 1.0 / fit.theta;
+        let predictions = (0..steps)
+            .map(|i| {
+                let trend = fit.intercept + fit.slope * (fit.n + i) as f64;
+                let adjusted = weight * trend + (1.0 - weight) * fit.level;
+                match &fit.seasonal {
+                    Some(indices) => adjusted * indices[i % indices.len()],
+                    None => adjusted,
+                }
+            })
+            .collect();
+
+        Ok(ForecastResult {
+            predictions,
+            lower_bound: None,
+            upper_bound: None,
+            confidence: 0.7,
+            intervals: Vec::new(),
+        })
+    }
+
+    /// Intervals use the SES forecast variance `σ²(1 + (h - 1)α²)` on the
+    /// seasonally adjusted scale, then are reseasonalised
+    fn forecast_with_confidence(&self, steps: usize, confidence_level: f64) -> Result<ForecastResult> {
+        let base_forecast = self.forecast(steps)?;
+        let fit = self.fit.as_ref().unwrap();
+
+        let std_errors: Vec<f64> = (0..steps)
+            .map(|i| {
+                let se = (fit.sigma2 * (1.0 + i as f64 * fit.alpha.powi(2))).sqrt();
+                match &fit.seasonal {
+                    Some(indices) => se * indices[i % indices.len()],
+                    None => se,
+                }
+            })
+            .collect();
+
+        ForecastResult::with_normal_interval(base_forecast, &std_errors, confidence_level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theta_trend() {
+        let data: Vec<f64> = (0..40).map(|t| 10.0 + 0.5 * t as f64 + (t as f64 * 1.3).sin()).collect();
+        let ts = TimeSeries::new(data);
+
+        let mut classical = Theta::new();
+        classical.fit(&ts).unwrap();
+        assert_eq!(classical.theta(), Some(2.0));
+        let forecast = classical.forecast_with_confidence(5, 0.95).unwrap();
+        assert!(forecast.predictions[4] > forecast.predictions[0]);
+        assert!(forecast.lower_bound.unwrap()[0] < forecast.predictions[0]);
+
+        // A steady trend favours θ > 2, i.e. more weight on the trend line
+        let mut optimized = Theta::optimized();
+        optimized.fit(&ts).unwrap();
+        assert!(optimized.theta().unwrap() > 2.0);
+        let forecast = optimized.forecast(5).unwrap();
+        let expected = 10.0 + 0.5 * 44.0;
+        assert!((forecast.predictions[4] - expected).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_theta_seasonal_adjustment() {
+        let pattern = [0.8, 1.0, 1.4, 0.8];
+        let data: Vec<f64> = (0..48).map(|t| (50.0 + t as f64) * pattern[t % 4]).collect();
+        let ts = TimeSeries::new(data);
+
+        let mut model = Theta::new().with_seasonal_period(4).unwrap();
+        model.fit(&ts).unwrap();
+        assert!(model.seasonally_adjusted());
+
+        // Forecasts start at position 48, i.e. pattern index 0
+        let forecast = model.forecast(4).unwrap().predictions;
+        assert!(forecast[2] > forecast[1] && forecast[1] > forecast[0]);
+        assert!(forecast[2] > forecast[3]);
+    }
+
+    #[test]
+    fn test_theta_invalid_input() {
+        assert!(Theta::new().with_seasonal_period(1).is_err());
+        assert!(Theta::new().forecast(1).is_err());
+        let mut model = Theta::new();
+        assert!(model.fit(&TimeSeries::new(vec![1.0, 2.0])).is_err());
+        assert!(model.fit(&TimeSeries::new(vec![1.0, f64::NAN, 2.0, 3.0])).is_err());
+    }
+}
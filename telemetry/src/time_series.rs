@@ -1,5 +1,6 @@
 //! Core time series data structure and operations
 
+use crate::rolling::Sweep;
 use crate::{Result, TelemetryError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,9 @@ impl TimeSeries {
     }
 
     /// Create a time series with timestamps
+    ///
+    /// Only the lengths are checked; see
+    /// [`TimeSeries::with_validated_timestamps`] to enforce ordering.
     pub fn with_timestamps(values: Vec<f64>, timestamps: Vec<DateTime<Utc>>) -> Result<Self> {
         if values.len() != timestamps.len() {
             return Err(TelemetryError::InvalidData(
@@ -55,7 +59,9 @@ impl TimeSeries {
         self.values.is_empty()
     }
 
-    /// Calculate simple moving average
+    /// Calculate simple moving average in O(n) with a running sum
+    ///
+    /// Windows containing a missing (`NaN`) value yield `NaN`.
     pub fn moving_average(&self, window: usize) -> Result<Vec<f64>> {
         if window == 0 {
             return Err(TelemetryError::InvalidParameter(
@@ -69,17 +75,19 @@ impl TimeSeries {
             ));
         }
 
-        let mut result = Vec::with_capacity(self.values.len() - window + 1);
-
-        for i in 0..=(self.values.len() - window) {
-            let sum: f64 = self.values[i..i + window].iter().sum();
-            result.push(sum / window as f64);
-        }
-
-        Ok(result)
+        let mut sweep = Sweep::new(&self.values, false);
+        Ok((0..=(self.values.len() - window))
+            .map(|i| {
+                let acc = sweep.advance(i, i + window);
+                if acc.missing() > 0 { f64::NAN } else { acc.mean() }
+            })
+            .collect())
     }
 
     /// Calculate exponential moving average
+    ///
+    /// Missing values carry the previous average forward; leading missing
+    /// values stay `NaN` until the first observation.
     pub fn exponential_moving_average(&self, alpha: f64) -> Result<Vec<f64>> {
         if alpha <= 0.0 || alpha > 1.0 {
             return Err(TelemetryError::InvalidParameter(
@@ -97,7 +105,14 @@ impl TimeSeries {
         result.push(self.values[0]);
 
         for i in 1..self.values.len() {
-            let ema = alpha * self.values[i] + (1.0 - alpha) * result[i - 1];
+            let prev = result[i - 1];
+            let ema = if self.values[i].is_nan() {
+                prev
+            } else if prev.is_nan() {
+                self.values[i]
+            } else {
+                alpha * self.values[i] + (1.0 - alpha) * prev
+            };
             result.push(ema);
         }
 
@@ -152,7 +167,7 @@ impl TimeSeries {
         })
     }
 
-    /// Calculate basic statistics
+    /// Calculate basic statistics, ignoring missing values
     pub fn statistics(&self) -> Statistics {
         Statistics::from_values(&self.values)
     }
@@ -166,11 +181,19 @@ pub struct Statistics {
     pub std_dev: f64,
     pub min: f64,
     pub max: f64,
+    /// Number of non-missing values
     pub count: usize,
+    /// Number of missing (`NaN`) values that were ignored
+    pub missing: usize,
 }
 
 impl Statistics {
+    /// Compute statistics over the non-missing values.
+    ///
+    /// If every value is missing, all statistics are zero.
     pub fn from_values(values: &[f64]) -> Self {
+        let missing = values.iter().filter(|v| v.is_nan()).count();
+        let values: Vec<f64> = values.iter().cloned().filter(|v| !v.is_nan()).collect();
         let count = values.len();
 
         if count == 0 {
@@ -181,15 +204,16 @@ impl Statistics {
                 min: 0.0,
                 max: 0.0,
                 count: 0,
+                missing,
             };
         }
 
         let mean = values.iter().sum::<f64>() / count as f64;
 
-        let mut sorted = values.to_vec();
+        let mut sorted = values.clone();
         sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
-        let median = if count % 2 == 0 {
+        let median = if count.is_multiple_of(2) {
             (sorted[count / 2 - 1] + sorted[count / 2]) / 2.0
         } else {
             sorted[count / 2]
@@ -210,10 +234,25 @@ impl Statistics {
             min,
             max,
             count,
+            missing,
         }
     }
 }
 
+/// Linearly interpolated quantile of already sorted values
+pub(crate) fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+
+    let pos = q * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    let frac = pos - lower as f64;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * frac
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,4 +280,13 @@ mod tests {
         assert_eq!(stats.min, 1.0);
         assert_eq!(stats.max, 5.0);
     }
+
+    #[test]
+    fn test_statistics_ignore_missing() {
+        let ts = TimeSeries::new(vec![1.0, f64::NAN, 3.0]);
+        let stats = ts.statistics();
+        assert_eq!(stats.mean, 2.0);
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.missing, 1);
+    }
 }
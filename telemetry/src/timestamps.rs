@@ -0,0 +1,258 @@
+//! Timestamp validation, ordering, deduplication and frequency inference
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+
+use crate::resample::{Aggregation, Frequency};
+use crate::{Result, TelemetryError, TimeSeries};
+
+/// How to treat out-of-order or duplicated timestamps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimestampPolicy {
+    /// Reject timestamps that are not strictly increasing
+    Strict,
+    /// Sort by time; reject duplicates
+    Sort,
+    /// Sort by time and merge duplicates with the aggregation
+    /// (missing values are ignored)
+    Deduplicate(Aggregation),
+}
+
+/// A run of missing intervals relative to a frequency
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    /// Start of the first missing interval
+    pub start: DateTime<Utc>,
+    /// Start of the next observed interval
+    pub end: DateTime<Utc>,
+    /// Number of missing intervals
+    pub missing: usize,
+}
+
+impl TimeSeries {
+    /// Create a time series with timestamps, enforcing `policy`
+    pub fn with_validated_timestamps(
+        values: Vec<f64>,
+        timestamps: Vec<DateTime<Utc>>,
+        policy: TimestampPolicy,
+    ) -> Result<Self> {
+        let ts = Self::with_timestamps(values, timestamps)?;
+        match policy {
+            TimestampPolicy::Strict => {
+                ts.validate_timestamps()?;
+                Ok(ts)
+            }
+            TimestampPolicy::Sort => {
+                let sorted = ts.sort_by_time()?;
+                sorted.validate_timestamps()?;
+                Ok(sorted)
+            }
+            TimestampPolicy::Deduplicate(agg) => ts.deduplicate(agg),
+        }
+    }
+
+    fn require_timestamps(&self) -> Result<&[DateTime<Utc>]> {
+        self.timestamps.as_deref().ok_or_else(|| {
+            TelemetryError::InvalidData("Time series has no timestamps".to_string())
+        })
+    }
+
+    /// Check that timestamps are present and strictly increasing
+    pub fn validate_timestamps(&self) -> Result<()> {
+        let timestamps = self.require_timestamps()?;
+        if let Some(i) = timestamps.windows(2).position(|w| w[0] >= w[1]) {
+            let problem = if timestamps[i] == timestamps[i + 1] { "Duplicate" } else { "Out-of-order" };
+            return Err(TelemetryError::InvalidData(format!(
+                "{} timestamp at index {}: {}",
+                problem,
+                i + 1,
+                timestamps[i + 1]
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reorder observations by timestamp (stable for equal timestamps)
+    pub fn sort_by_time(&self) -> Result<TimeSeries> {
+        let timestamps = self.require_timestamps()?;
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|&i| timestamps[i]);
+
+        Ok(TimeSeries {
+            values: order.iter().map(|&i| self.values[i]).collect(),
+            timestamps: Some(order.iter().map(|&i| timestamps[i]).collect()),
+            name: self.name.clone(),
+        })
+    }
+
+    /// Sort by time and merge observations sharing a timestamp with `agg`
+    pub fn deduplicate(&self, agg: Aggregation) -> Result<TimeSeries> {
+        agg.validate()?;
+        let sorted = self.sort_by_time()?;
+        let timestamps = sorted.timestamps.as_ref().unwrap();
+
+        let mut values = Vec::new();
+        let mut unique = Vec::new();
+        let mut start = 0;
+        while start < timestamps.len() {
+            let end = start + timestamps[start..].iter().take_while(|t| **t == timestamps[start]).count();
+            let group: Vec<f64> = sorted.values[start..end].iter().copied().filter(|v| !v.is_nan()).collect();
+            values.push(agg.apply(&group));
+            unique.push(timestamps[start]);
+            start = end;
+        }
+
+        Ok(TimeSeries {
+            values,
+            timestamps: Some(unique),
+            name: self.name.clone(),
+        })
+    }
+
+    /// Infer the sampling frequency from the most common interval
+    ///
+    /// Intervals of one calendar month are recognised as [`Frequency::Month`]
+    /// and exact minutes, hours, days and weeks map to their calendar
+    /// variants; any other modal interval becomes [`Frequency::Fixed`].
+    /// Timestamps must be strictly increasing.
+    pub fn infer_frequency(&self) -> Result<Frequency> {
+        self.validate_timestamps()?;
+        let timestamps = self.require_timestamps()?;
+        if timestamps.len() < 2 {
+            return Err(TelemetryError::InsufficientData(
+                "Need at least 2 timestamps to infer a frequency".to_string(),
+            ));
+        }
+
+        let pairs = timestamps.len() - 1;
+        let monthly = timestamps
+            .windows(2)
+            .filter(|w| {
+                let days = (w[1] - w[0]).num_days();
+                month_index(w[1]) - month_index(w[0]) == 1 && (28..=31).contains(&days)
+            })
+            .count();
+        if 2 * monthly > pairs {
+            return Ok(Frequency::Month);
+        }
+
+        let mut counts: HashMap<i64, usize> = HashMap::new();
+        for w in timestamps.windows(2) {
+            *counts.entry((w[1] - w[0]).num_milliseconds()).or_insert(0) += 1;
+        }
+        // Most common interval; ties go to the shorter one
+        let (&mode, _) = counts
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .unwrap();
+
+        let interval = Duration::milliseconds(mode);
+        Ok(if interval == Duration::minutes(1) {
+            Frequency::Minute
+        } else if interval == Duration::hours(1) {
+            Frequency::Hour
+        } else if interval == Duration::days(1) {
+            Frequency::Day
+        } else if interval == Duration::weeks(1) {
+            Frequency::Week
+        } else {
+            Frequency::Fixed(interval)
+        })
+    }
+
+    /// Missing intervals between consecutive observations at frequency `freq`
+    /// (use [`TimeSeries::infer_frequency`] when it is not known)
+    pub fn gaps(&self, freq: Frequency) -> Result<Vec<Gap>> {
+        freq.validate()?;
+        self.validate_timestamps()?;
+        let timestamps = self.require_timestamps()?;
+
+        let mut gaps = Vec::new();
+        for w in timestamps.windows(2) {
+            let (a, b) = (freq.floor(w[0]), freq.floor(w[1]));
+            let steps = match freq {
+                Frequency::Month => (month_index(b) - month_index(a)) as i64,
+                Frequency::Fixed(d) => (b - a).num_milliseconds() / d.num_milliseconds(),
+                Frequency::Minute => (b - a).num_minutes(),
+                Frequency::Hour => (b - a).num_hours(),
+                Frequency::Day => (b - a).num_days(),
+                Frequency::Week => (b - a).num_weeks(),
+            };
+            if steps > 1 {
+                gaps.push(Gap {
+                    start: freq.next(a),
+                    end: b,
+                    missing: (steps - 1) as usize,
+                });
+            }
+        }
+        Ok(gaps)
+    }
+}
+
+fn month_index(t: DateTime<Utc>) -> i32 {
+    t.year() * 12 + t.month() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minutes: &[i64]) -> Vec<DateTime<Utc>> {
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        minutes.iter().map(|m| start + Duration::minutes(*m)).collect()
+    }
+
+    #[test]
+    fn test_validation_policies() {
+        let timestamps = at(&[30, 0, 15, 15]);
+        let values = vec![3.0, 1.0, 2.0, 4.0];
+
+        assert!(TimeSeries::with_validated_timestamps(values.clone(), timestamps.clone(), TimestampPolicy::Strict).is_err());
+        assert!(TimeSeries::with_validated_timestamps(values.clone(), timestamps.clone(), TimestampPolicy::Sort).is_err());
+
+        let merged = TimeSeries::with_validated_timestamps(
+            values,
+            timestamps,
+            TimestampPolicy::Deduplicate(Aggregation::Mean),
+        )
+        .unwrap();
+        assert_eq!(merged.values, vec![1.0, 3.0, 3.0]);
+        assert_eq!(merged.timestamps.unwrap(), at(&[0, 15, 30]));
+
+        assert!(TimeSeries::with_validated_timestamps(
+            vec![1.0, 2.0],
+            at(&[0, 0]),
+            TimestampPolicy::Deduplicate(Aggregation::Quantile(1.5)),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_infer_frequency() {
+        let quarter_hourly = TimeSeries::with_timestamps(vec![0.0; 5], at(&[0, 15, 30, 60, 75])).unwrap();
+        assert_eq!(quarter_hourly.infer_frequency().unwrap(), Frequency::Fixed(Duration::minutes(15)));
+
+        let daily = TimeSeries::with_timestamps(vec![0.0; 3], at(&[0, 1440, 2880])).unwrap();
+        assert_eq!(daily.infer_frequency().unwrap(), Frequency::Day);
+
+        let months: Vec<_> = (1..=6).map(|m| Utc.with_ymd_and_hms(2023, m, 15, 0, 0, 0).unwrap()).collect();
+        let monthly = TimeSeries::with_timestamps(vec![0.0; 6], months).unwrap();
+        assert_eq!(monthly.infer_frequency().unwrap(), Frequency::Month);
+
+        assert!(TimeSeries::new(vec![1.0, 2.0]).infer_frequency().is_err());
+    }
+
+    #[test]
+    fn test_gap_report() {
+        let ts = TimeSeries::with_timestamps(vec![0.0; 5], at(&[0, 15, 30, 75, 135])).unwrap();
+        let freq = ts.infer_frequency().unwrap();
+        let gaps = ts.gaps(freq).unwrap();
+
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(gaps[0], Gap { start: at(&[45])[0], end: at(&[75])[0], missing: 2 });
+        assert_eq!(gaps[1].missing, 3);
+    }
+}
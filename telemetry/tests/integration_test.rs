@@ -2,7 +2,7 @@
 
 use avila_telemetry::{
     TimeSeries, AnomalyDetector, Forecaster, ExponentialSmoothing,
-    FeatureExtractor, Decomposer, DecompositionType,
+    FeatureExtractor, Decomposer, DecompositionType, Holt,
 };
 
 #[test]
@@ -117,3 +117,19 @@ fn test_forecast_with_confidence_intervals() {
         assert!(upper[i] > forecast.predictions[i]);
     }
 }
+
+#[test]
+fn test_holt_forecasts_trend() {
+    let data: Vec<f64> = (1..=30).map(|x| x as f64).collect();
+    let ts = TimeSeries::new(data);
+
+    let mut forecaster = Holt::new();
+    forecaster.fit(&ts).unwrap();
+
+    let forecast = forecaster.forecast(5).unwrap();
+
+    // Unlike simple exponential smoothing, the forecast keeps rising
+    for window in forecast.predictions.windows(2) {
+        assert!(window[1] > window[0]);
+    }
+}